@@ -0,0 +1,258 @@
+use crate::checksum;
+use crate::error::Error;
+use crate::hash::{fnv1a_32, KeyHasher};
+use byteorder::ByteOrder;
+
+const RESERVED_SIZE_FOR_NUMBER_OF_BITS: usize = size_of::<u32>();
+const RESERVED_SIZE_FOR_NUMBER_OF_HASHES: usize = size_of::<u8>();
+const RESERVED_SIZE_FOR_HEADER: usize =
+    RESERVED_SIZE_FOR_NUMBER_OF_BITS + RESERVED_SIZE_FOR_NUMBER_OF_HASHES;
+
+// The buffer holds the encoded header followed by the bits, so encoding is free.
+pub(crate) struct BloomFilter<H: KeyHasher = Crc32Fnv1aHasher> {
+    buffer: Vec<u8>,
+    number_of_bits: usize,
+    number_of_hashes: u8,
+    hasher: H,
+}
+
+// Part of the on-disk format: the bits of an encoded filter were set using this exact hash, so
+// changing it breaks lookups in existing filters. The high half is checksum::crc32 and the low
+// half is the 32-bit FNV-1a.
+#[derive(Default)]
+pub(crate) struct Crc32Fnv1aHasher;
+
+impl KeyHasher for Crc32Fnv1aHasher {
+    fn hash(&self, key: &[u8]) -> u64 {
+        (checksum::crc32(key) as u64) << 32 | fnv1a_32(key) as u64
+    }
+}
+
+impl BloomFilter {
+    pub(crate) fn new(number_of_bits: usize, number_of_hashes: u8) -> Result<Self, Error> {
+        Self::with_hasher(number_of_bits, number_of_hashes, Crc32Fnv1aHasher)
+    }
+
+    pub(crate) fn from_keys<'k, I: IntoIterator<Item = &'k [u8]>>(
+        keys: I,
+        number_of_bits: usize,
+        number_of_hashes: u8,
+    ) -> Result<Self, Error> {
+        let mut bloom_filter = Self::new(number_of_bits, number_of_hashes)?;
+        keys.into_iter().for_each(|key| bloom_filter.add(key));
+        Ok(bloom_filter)
+    }
+}
+
+impl<H: KeyHasher> BloomFilter<H> {
+    pub(crate) fn with_hasher(
+        number_of_bits: usize,
+        number_of_hashes: u8,
+        hasher: H,
+    ) -> Result<Self, Error> {
+        if number_of_bits == 0 || number_of_hashes == 0 {
+            return Err(Error::InvalidBloomFilter {
+                number_of_bits,
+                number_of_hashes,
+            });
+        }
+        if number_of_bits > u32::MAX as usize {
+            return Err(Error::ValueTooLarge {
+                length: number_of_bits,
+                maximum: u32::MAX as usize,
+            });
+        }
+        let mut buffer = vec![0; RESERVED_SIZE_FOR_HEADER + number_of_bits.div_ceil(8)];
+        byteorder::LittleEndian::write_u32(&mut buffer, number_of_bits as u32);
+        buffer[RESERVED_SIZE_FOR_NUMBER_OF_BITS] = number_of_hashes;
+        Ok(BloomFilter {
+            buffer,
+            number_of_bits,
+            number_of_hashes,
+            hasher,
+        })
+    }
+
+    pub(crate) fn add(&mut self, key: &[u8]) {
+        for bit_index in self.bit_indices(key) {
+            self.buffer[RESERVED_SIZE_FOR_HEADER + bit_index / 8] |= 1 << (bit_index % 8);
+        }
+    }
+
+    pub(crate) fn might_contain(&self, key: &[u8]) -> bool {
+        self.bit_indices(key).all(|bit_index| {
+            self.buffer[RESERVED_SIZE_FOR_HEADER + bit_index / 8] & (1 << (bit_index % 8)) != 0
+        })
+    }
+
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        self.buffer.len()
+    }
+
+    // Uses double hashing over the two halves of the hash: the i-th index is h1 + i * h2, which
+    // behaves like independent hashes.
+    fn bit_indices(&self, key: &[u8]) -> impl Iterator<Item = usize> {
+        let hash = self.hasher.hash(key);
+        let first_hash = hash >> 32;
+        let second_hash = hash & u32::MAX as u64 | 1;
+        let number_of_bits = self.number_of_bits as u64;
+        (0..self.number_of_hashes as u64).map(move |hash_number| {
+            (first_hash.wrapping_add(hash_number.wrapping_mul(second_hash)) % number_of_bits)
+                as usize
+        })
+    }
+}
+
+impl<H: KeyHasher + Default> crate::page::Page for BloomFilter<H> {
+    fn decode_from(mut buffer: Vec<u8>) -> Result<Self, Error> {
+        if buffer.len() < RESERVED_SIZE_FOR_HEADER {
+            return Err(Error::Decode(
+                "buffer is too small to hold a bloom filter".to_string(),
+            ));
+        }
+        let number_of_bits = byteorder::LittleEndian::read_u32(&buffer) as usize;
+        let number_of_hashes = buffer[RESERVED_SIZE_FOR_NUMBER_OF_BITS];
+        let end_of_bits = RESERVED_SIZE_FOR_HEADER + number_of_bits.div_ceil(8);
+        if number_of_bits == 0 || number_of_hashes == 0 || end_of_bits > buffer.len() {
+            return Err(Error::Corrupt(format!(
+                "bloom filter of {} bits and {} hashes does not fit in {} bytes",
+                number_of_bits,
+                number_of_hashes,
+                buffer.len()
+            )));
+        }
+        buffer.truncate(end_of_bits);
+        Ok(BloomFilter {
+            buffer,
+            number_of_bits,
+            number_of_hashes,
+            hasher: H::default(),
+        })
+    }
+
+    fn encode(&mut self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bloom::BloomFilter;
+    use crate::error::Error;
+    use crate::file::file_manager::FileManager;
+    use crate::hash::Fnv1aHasher;
+    use crate::page::Page;
+    use std::ops::Range;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    fn keys(range: Range<usize>) -> Vec<Vec<u8>> {
+        range
+            .map(|key| format!("key-{}", key).into_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn no_false_negatives_for_added_keys() {
+        let keys = keys(0..1000);
+        let bloom_filter =
+            BloomFilter::from_keys(keys.iter().map(Vec::as_slice), 10_000, 7).unwrap();
+
+        assert!(keys.iter().all(|key| bloom_filter.might_contain(key)));
+    }
+
+    #[test]
+    fn reasonable_false_positive_rate_for_absent_keys() {
+        let bloom_filter =
+            BloomFilter::from_keys(keys(0..1000).iter().map(Vec::as_slice), 10_000, 7).unwrap();
+
+        let false_positives = keys(1000..11_000)
+            .iter()
+            .filter(|key| bloom_filter.might_contain(key))
+            .count();
+        assert!(false_positives < 500, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn empty_bloom_filter_contains_nothing() {
+        let bloom_filter = BloomFilter::new(64, 3).unwrap();
+        assert!(!bloom_filter.might_contain(b"RocksDB"));
+    }
+
+    #[test]
+    fn encode_the_same_bits_with_the_default_hasher() {
+        let keys = keys(0..4);
+        let mut bloom_filter =
+            BloomFilter::from_keys(keys.iter().map(Vec::as_slice), 64, 3).unwrap();
+
+        assert_eq!(
+            &[64, 0, 0, 0, 3, 35, 1, 64, 65, 64, 16, 136, 4],
+            bloom_filter.encode()
+        );
+    }
+
+    #[test]
+    fn no_false_negatives_with_a_custom_hasher() {
+        let keys = keys(0..1000);
+        let mut bloom_filter = BloomFilter::with_hasher(10_000, 7, Fnv1aHasher).unwrap();
+        keys.iter().for_each(|key| bloom_filter.add(key));
+
+        assert!(keys.iter().all(|key| bloom_filter.might_contain(key)));
+        assert!(matches!(
+            BloomFilter::with_hasher(0, 7, Fnv1aHasher),
+            Err(Error::InvalidBloomFilter { .. })
+        ));
+    }
+
+    #[test]
+    fn write_a_bloom_filter_to_a_sidecar_block_and_read_it_back() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let bloom_file_name = format!("{}.bloom", file_name);
+
+        let keys = keys(0..100);
+        let mut bloom_filter =
+            BloomFilter::from_keys(keys.iter().map(Vec::as_slice), 1024, 5).unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = file_manager
+            .append_block(&bloom_file_name, bloom_filter.encode())
+            .unwrap();
+
+        let mut decoded = file_manager.read::<BloomFilter>(&block_id).unwrap();
+        assert!(keys.iter().all(|key| decoded.might_contain(key)));
+        assert_eq!(bloom_filter.encode(), decoded.encode());
+        assert_eq!(5 + 1024 / 8, decoded.size_in_bytes());
+    }
+
+    #[test]
+    fn attempt_to_create_a_bloom_filter_without_bits_or_hashes() {
+        assert!(matches!(
+            BloomFilter::new(0, 3),
+            Err(Error::InvalidBloomFilter {
+                number_of_bits: 0,
+                number_of_hashes: 3
+            })
+        ));
+        assert!(matches!(
+            BloomFilter::from_keys([b"RocksDB".as_slice()], 64, 0),
+            Err(Error::InvalidBloomFilter {
+                number_of_bits: 64,
+                number_of_hashes: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_a_bloom_filter_larger_than_the_buffer() {
+        let mut encoded = BloomFilter::new(1024, 3).unwrap().encode().to_vec();
+        encoded.truncate(64);
+
+        assert!(matches!(
+            <BloomFilter>::decode_from(encoded),
+            Err(Error::Corrupt(_))
+        ));
+    }
+}
@@ -1,103 +1,236 @@
 use crate::buffer::Buffer;
+use crate::error::Error;
 use crate::file::block_id::BlockId;
 use crate::log::log_manager::LogManager;
-use std::error::Error;
-use std::fmt::{Display, Formatter};
-use std::io;
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-#[derive(Debug)]
-enum BufferPinError {
-    IO(io::Error),
-    Unavailable,
+pub(crate) struct BufferManager<'l, 'a, PathType: AsRef<Path>> {
+    buffer_pool: Vec<RwLock<Buffer>>,
+    pool_state: Mutex<PoolState>,
+    log_manager: Mutex<&'l mut LogManager<'a, PathType>>,
+    write_through: bool,
 }
 
-impl BufferPinError {
-    fn is_unavailable_error(&self) -> bool {
-        if let BufferPinError::Unavailable = self {
-            return true;
-        }
-        false
-    }
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BufferStats {
+    pub(crate) hits: usize,
+    pub(crate) misses: usize,
+    pub(crate) evictions: usize,
 }
 
-impl From<io::Error> for BufferPinError {
-    fn from(error: io::Error) -> Self {
-        BufferPinError::IO(error)
-    }
+// Tracks which block each buffer holds and how many pins it has, so that a buffer can be
+// chosen without locking buffers that other threads are working on.
+struct PoolState {
+    frames: Vec<Frame>,
+    available_buffers: usize,
+    stats: BufferStats,
+    clock: usize,
 }
 
-impl Display for BufferPinError {
-    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            BufferPinError::IO(err) => write!(formatter, "Buffer I/O error: {}", err),
-            BufferPinError::Unavailable => write!(formatter, "Buffer is unavailable"),
-        }
-    }
+#[derive(Default)]
+struct Frame {
+    block_id: Option<BlockId>,
+    pins: usize,
+    last_pinned_at: usize,
 }
 
-impl Error for BufferPinError {}
-
-struct BufferManager<'a, PathType: AsRef<Path>> {
-    buffer_pool: Vec<Buffer>,
-    log_manager: &'a mut LogManager<'a, PathType>,
-    available_buffers: usize,
+// A pin only keeps the buffer from being evicted. The buffer itself is locked for as long as
+// a guard returned by `read` or `write` is held, so the same block can be pinned more than once
+// and `flush_all` can run while pins are outstanding.
+pub(crate) struct PinnedBuffer<'m, 'l, 'a, PathType: AsRef<Path>> {
+    buffer_manager: &'m BufferManager<'l, 'a, PathType>,
+    buffer_index: usize,
 }
 
-impl<'a, PathType: AsRef<Path>> BufferManager<'a, PathType> {
-    fn new(
+impl<'l, 'a, PathType: AsRef<Path>> BufferManager<'l, 'a, PathType> {
+    pub(crate) fn new(
         capacity: usize,
-        log_manager: &'a mut LogManager<'a, PathType>,
-    ) -> BufferManager<'a, PathType> {
-        BufferManager {
-            buffer_pool: vec![Buffer::new()],
-            log_manager,
-            available_buffers: capacity,
-        }
+        log_manager: &'l mut LogManager<'a, PathType>,
+    ) -> BufferManager<'l, 'a, PathType> {
+        Self::new_with_write_through(capacity, log_manager, false)
     }
 
-    fn pin(&mut self, block_id: BlockId) -> Result<&mut Buffer, BufferPinError> {
-        self.try_pin(block_id)
+    /// With write-through, a modified buffer is written to disk as soon as it is unpinned.
+    pub(crate) fn new_with_write_through(
+        capacity: usize,
+        log_manager: &'l mut LogManager<'a, PathType>,
+        write_through: bool,
+    ) -> BufferManager<'l, 'a, PathType> {
+        BufferManager {
+            buffer_pool: (0..capacity).map(|_| RwLock::new(Buffer::new())).collect(),
+            pool_state: Mutex::new(PoolState {
+                frames: (0..capacity).map(|_| Frame::default()).collect(),
+                available_buffers: capacity,
+                stats: BufferStats::default(),
+                clock: 0,
+            }),
+            log_manager: Mutex::new(log_manager),
+            write_through,
+        }
     }
 
-    fn unpin(&mut self, block_id: &BlockId) {
-        for buffer in self.buffer_pool.iter_mut() {
-            if buffer.has_block_id(block_id) {
-                buffer.unpin();
-                if !buffer.is_pinned() {
-                    self.available_buffers += 1;
+    pub(crate) fn pin(
+        &self,
+        block_id: BlockId,
+    ) -> Result<PinnedBuffer<'_, 'l, 'a, PathType>, Error> {
+        loop {
+            let mut pool_state = self.pool_state.lock().unwrap();
+            let buffer_index = pool_state.buffer_index_for(&block_id)?;
+
+            if pool_state.frames[buffer_index].block_id.as_ref() == Some(&block_id) {
+                pool_state.pin_frame(buffer_index);
+                pool_state.stats.hits += 1;
+                drop(pool_state);
+
+                // Another thread may still be reading the block into this buffer; its write
+                // lock is held until the read finishes. If that read failed, the frame went
+                // back to its previous block and the pin is retried.
+                let mut buffer = self.buffer_pool[buffer_index].write().unwrap();
+                if buffer.block_id() == Some(&block_id) {
+                    buffer.pin();
+                    return Ok(self.pinned(buffer_index));
                 }
-                return;
+                drop(buffer);
+                self.unpin(buffer_index);
+                continue;
             }
-        }
-    }
 
-    fn try_pin(&mut self, block_id: BlockId) -> Result<&mut Buffer, BufferPinError> {
-        for buffer in self.buffer_pool.iter_mut() {
-            if buffer.has_block_id(&block_id) {
-                if !buffer.is_pinned() {
-                    self.available_buffers -= 1;
-                }
+            // The frame is unpinned, so no one else holds its lock for long. Taking the lock
+            // before releasing the pool makes other threads pinning the same block wait for
+            // the read below.
+            let mut buffer = self.buffer_pool[buffer_index].write().unwrap();
+            let previous_block_id = pool_state.frames[buffer_index]
+                .block_id
+                .replace(block_id.clone());
+            pool_state.pin_frame(buffer_index);
+            drop(pool_state);
+
+            let assigned = buffer.assign_to_block(block_id, &mut self.log_manager());
+            if assigned.is_ok() {
                 buffer.pin();
-                return Ok(buffer);
             }
-            if !buffer.is_pinned() {
-                buffer.assign_to_block(block_id, self.log_manager)?;
-                self.available_buffers -= 1;
-                buffer.pin();
-                return Ok(buffer);
+            drop(buffer);
+
+            let mut pool_state = self.pool_state.lock().unwrap();
+            if let Err(err) = assigned {
+                pool_state.frames[buffer_index].block_id = previous_block_id;
+                pool_state.unpin_frame(buffer_index);
+                return Err(err);
             }
+            if previous_block_id.is_some() {
+                pool_state.stats.evictions += 1;
+            }
+            pool_state.stats.misses += 1;
+            return Ok(self.pinned(buffer_index));
+        }
+    }
+
+    pub(crate) fn flush_all(&self) -> Result<(), Error> {
+        for buffer in &self.buffer_pool {
+            buffer.write().unwrap().flush(&mut self.log_manager())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn available_buffers(&self) -> usize {
+        self.pool_state.lock().unwrap().available_buffers
+    }
+
+    pub(crate) fn stats(&self) -> BufferStats {
+        self.pool_state.lock().unwrap().stats
+    }
+
+    pub(crate) fn log_manager(&self) -> MutexGuard<'_, &'l mut LogManager<'a, PathType>> {
+        self.log_manager.lock().unwrap()
+    }
+
+    fn pinned(&self, buffer_index: usize) -> PinnedBuffer<'_, 'l, 'a, PathType> {
+        PinnedBuffer {
+            buffer_manager: self,
+            buffer_index,
+        }
+    }
+
+    fn unpin(&self, buffer_index: usize) {
+        self.pool_state.lock().unwrap().unpin_frame(buffer_index);
+    }
+}
+
+impl PoolState {
+    // Evicts the least recently pinned buffer among the unpinned ones. Buffers that were never
+    // used have not been pinned since the clock started, so they are chosen first.
+    fn buffer_index_for(&self, block_id: &BlockId) -> Result<usize, Error> {
+        self.frames
+            .iter()
+            .position(|frame| frame.block_id.as_ref() == Some(block_id))
+            .or_else(|| {
+                self.frames
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, frame)| frame.pins == 0)
+                    .min_by_key(|(_, frame)| frame.last_pinned_at)
+                    .map(|(buffer_index, _)| buffer_index)
+            })
+            .ok_or(Error::BufferUnavailable)
+    }
+
+    fn pin_frame(&mut self, buffer_index: usize) {
+        if self.frames[buffer_index].pins == 0 {
+            self.available_buffers -= 1;
+        }
+        self.clock += 1;
+        self.frames[buffer_index].pins += 1;
+        self.frames[buffer_index].last_pinned_at = self.clock;
+    }
+
+    fn unpin_frame(&mut self, buffer_index: usize) {
+        self.frames[buffer_index].pins -= 1;
+        if self.frames[buffer_index].pins == 0 {
+            self.available_buffers += 1;
         }
-        Err(BufferPinError::Unavailable)
+    }
+}
+
+impl<PathType: AsRef<Path>> PinnedBuffer<'_, '_, '_, PathType> {
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, Buffer> {
+        self.buffer_manager.buffer_pool[self.buffer_index]
+            .read()
+            .unwrap()
+    }
+
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, Buffer> {
+        self.buffer_manager.buffer_pool[self.buffer_index]
+            .write()
+            .unwrap()
+    }
+}
+
+impl<PathType: AsRef<Path>> Drop for PinnedBuffer<'_, '_, '_, PathType> {
+    fn drop(&mut self) {
+        {
+            let mut buffer = self.write();
+            buffer.unpin();
+            if self.buffer_manager.write_through {
+                // Drop cannot report the failure; the buffer stays modified and is written
+                // again on the next flush or when it is reassigned.
+                let _ = buffer.flush(&mut self.buffer_manager.log_manager());
+            }
+        }
+        self.buffer_manager.unpin(self.buffer_index);
     }
 }
 
 #[cfg(test)]
 mod buffer_manager_tests {
-    use crate::buffer::buffer_manager::BufferManager;
+    use crate::buffer::buffer_manager::{BufferManager, BufferStats};
+    use crate::buffer::page::BufferPage;
+    use crate::error::Error;
     use crate::file::block_id::BlockId;
     use crate::file::file_manager::FileManager;
     use crate::log::log_manager::LogManager;
+    use std::sync::Arc;
+    use std::thread;
     use tempfile::NamedTempFile;
 
     const BLOCK_SIZE: usize = 4096;
@@ -110,16 +243,19 @@ mod buffer_manager_tests {
         let log_file_name = format!("{}.log", buffer_file_name);
 
         let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
-        let mut buffer_manager = BufferManager::new(1, &mut log_manager);
-        buffer_manager.buffer_pool[0].pin();
-
-        assert!(buffer_manager
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        let _pinned = buffer_manager
             .pin(BlockId::new(buffer_file_name, 0))
-            .err()
-            .unwrap()
-            .is_unavailable_error());
+            .unwrap();
+
+        assert!(matches!(
+            buffer_manager.pin(BlockId::new(buffer_file_name, 1)),
+            Err(Error::BufferUnavailable)
+        ));
     }
 
     #[test]
@@ -133,7 +269,7 @@ mod buffer_manager_tests {
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
         let buffer_manager = BufferManager::new(1, &mut log_manager);
-        assert_eq!(1, buffer_manager.available_buffers);
+        assert_eq!(1, buffer_manager.available_buffers());
     }
 
     #[test]
@@ -144,15 +280,16 @@ mod buffer_manager_tests {
         let log_file_name = format!("{}.log", buffer_file_name);
 
         let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
-        let mut buffer_manager = BufferManager::new(1, &mut log_manager);
-        let buffer = buffer_manager
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        let pinned = buffer_manager
             .pin(BlockId::new(buffer_file_name, 0))
             .unwrap();
 
-        assert!(buffer.is_pinned());
-        assert_eq!(0, buffer_manager.available_buffers);
+        assert!(pinned.read().is_pinned());
+        assert_eq!(0, buffer_manager.available_buffers());
     }
 
     #[test]
@@ -163,20 +300,21 @@ mod buffer_manager_tests {
         let log_file_name = format!("{}.log", buffer_file_name);
 
         let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
-        let mut buffer_manager = BufferManager::new(1, &mut log_manager);
-        let _ = buffer_manager
-            .pin(BlockId::new(buffer_file_name, 0))
-            .unwrap();
-
-        buffer_manager.unpin(&BlockId::new(buffer_file_name, 0));
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        drop(
+            buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap(),
+        );
 
-        let _ = buffer_manager
+        let _pinned = buffer_manager
             .pin(BlockId::new(buffer_file_name, 0))
             .unwrap();
 
-        assert_eq!(0, buffer_manager.available_buffers);
+        assert_eq!(0, buffer_manager.available_buffers());
     }
 
     #[test]
@@ -187,85 +325,405 @@ mod buffer_manager_tests {
         let log_file_name = format!("{}.log", buffer_file_name);
 
         let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
-        let mut buffer_manager = BufferManager::new(1, &mut log_manager);
-        let buffer = buffer_manager
-            .pin(BlockId::new(buffer_file_name, 0))
-            .unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        {
+            let pinned = buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap();
+
+            let mut buffer = pinned.write();
 
-        let page = buffer.page().unwrap();
-        page.add_string("RocksDB is an LSM based storage engine");
-        page.add_u16(250);
+            let page = buffer.page().unwrap();
+            page.add_string("RocksDB is an LSM based storage engine")
+                .unwrap();
+            page.add_u16(250).unwrap();
 
-        buffer.set_modified(10, 100);
+            buffer.set_modified(10, 100);
+        }
         {
             //simulate flush
-            buffer_manager.unpin(&BlockId::new(buffer_file_name, 0));
             let _ = buffer_manager
                 .pin(BlockId::new(buffer_file_name, 1))
                 .unwrap();
-
-            buffer_manager.unpin(&BlockId::new(buffer_file_name, 1));
         }
 
         let pinned = buffer_manager
             .pin(BlockId::new(buffer_file_name, 0))
             .unwrap();
 
-        let pinned_page = pinned.page();
-        let reassigned_buffer_page = pinned_page.as_ref().unwrap();
+        let mut buffer = pinned.write();
+        let reassigned_buffer_page = buffer.page().unwrap();
         assert_eq!(
             Some("RocksDB is an LSM based storage engine"),
-            reassigned_buffer_page.get_string(0)
+            reassigned_buffer_page.get_string(0).unwrap()
         );
-        assert_eq!(250, reassigned_buffer_page.get_u16(1).unwrap());
+        assert_eq!(Some(250), reassigned_buffer_page.get_u16(1).unwrap());
     }
-}
 
-#[cfg(test)]
-mod buffer_pin_error_tests {
-    use crate::buffer::buffer_manager::BufferPinError;
-    use std::io;
-    use std::io::{Error, ErrorKind};
+    #[test]
+    fn unpin_a_buffer_on_drop() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        {
+            let pinned = buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap();
+            assert!(pinned.read().is_pinned());
+
+            let mut buffer = pinned.write();
+            let page = buffer.page().unwrap();
+            page.add_u16(250).unwrap();
+            assert_eq!(Some(250), page.get_u16(0).unwrap());
+        }
+
+        assert_eq!(1, buffer_manager.available_buffers());
+        assert!(!buffer_manager.buffer_pool[0].read().unwrap().is_pinned());
+    }
 
     #[test]
-    fn error_is_buffer_unavailable() {
-        assert!(BufferPinError::Unavailable.is_unavailable_error());
+    fn count_hits_misses_and_evictions() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        drop(
+            buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap(),
+        );
+        assert_eq!(
+            BufferStats {
+                hits: 0,
+                misses: 1,
+                evictions: 0
+            },
+            buffer_manager.stats()
+        );
+
+        drop(
+            buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap(),
+        );
+        assert_eq!(1, buffer_manager.stats().hits);
+        assert_eq!(1, buffer_manager.stats().misses);
+
+        let _pinned = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 1))
+            .unwrap();
+        assert_eq!(
+            BufferStats {
+                hits: 1,
+                misses: 2,
+                evictions: 1
+            },
+            buffer_manager.stats()
+        );
     }
 
     #[test]
-    fn error_is_an_io_error() {
-        assert!(
-            !BufferPinError::IO(Error::new(ErrorKind::NotFound, "test error"))
-                .is_unavailable_error()
+    fn flush_all_buffers() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        {
+            let pinned = buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap();
+
+            let mut buffer = pinned.write();
+
+            let page = buffer.page().unwrap();
+            page.add_string("RocksDB is an LSM based storage engine")
+                .unwrap();
+            buffer.set_modified(10, 100);
+        }
+        assert!(buffer_manager.flush_all().is_ok());
+
+        let page = file_manager
+            .read::<BufferPage>(&BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        assert_eq!(
+            Some("RocksDB is an LSM based storage engine"),
+            page.get_string(0).unwrap()
         );
     }
 
     #[test]
-    fn buffer_pin_error_from_io_error() {
-        let io_error = Error::new(ErrorKind::NotFound, "test error");
-        let buffer_pin_error = BufferPinError::from(io_error);
-        match buffer_pin_error {
-            BufferPinError::IO(err) => assert_eq!(ErrorKind::NotFound, err.kind()),
-            BufferPinError::Unavailable => panic!("unexpected error"),
+    fn pin_a_block_held_by_the_last_buffer_without_reading_it_again() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        (0..3).for_each(|_| {
+            file_manager.append_empty_block(buffer_file_name).unwrap();
+        });
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(3, &mut log_manager);
+        let pinned: Vec<_> = (0..3)
+            .map(|block_number| {
+                buffer_manager
+                    .pin(BlockId::new(buffer_file_name, block_number))
+                    .unwrap()
+            })
+            .collect();
+        drop(pinned);
+        let reads = file_manager.io_stats().reads;
+
+        let pinned = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 2))
+            .unwrap();
+        assert_eq!(2, pinned.buffer_index);
+        assert_eq!(reads, file_manager.io_stats().reads);
+        assert_eq!(1, buffer_manager.stats().hits);
+        assert_eq!(0, buffer_manager.stats().evictions);
+    }
+
+    #[test]
+    fn evict_the_least_recently_pinned_unpinned_buffer() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        (0..4).for_each(|_| {
+            file_manager.append_empty_block(buffer_file_name).unwrap();
+        });
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(3, &mut log_manager);
+        let least_recently_pinned = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        drop(
+            buffer_manager
+                .pin(BlockId::new(buffer_file_name, 1))
+                .unwrap(),
+        );
+        drop(
+            buffer_manager
+                .pin(BlockId::new(buffer_file_name, 2))
+                .unwrap(),
+        );
+
+        let pinned = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 3))
+            .unwrap();
+        assert_eq!(1, pinned.buffer_index);
+        assert_eq!(0, least_recently_pinned.buffer_index);
+        drop(pinned);
+
+        drop(
+            buffer_manager
+                .pin(BlockId::new(buffer_file_name, 2))
+                .unwrap(),
+        );
+        assert_eq!(
+            BufferStats {
+                hits: 1,
+                misses: 4,
+                evictions: 1
+            },
+            buffer_manager.stats()
+        );
+    }
+
+    #[test]
+    fn write_a_modified_buffer_through_on_unpin() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new_with_write_through(1, &mut log_manager, true);
+        {
+            let pinned = buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap();
+
+            let mut buffer = pinned.write();
+
+            let page = buffer.page().unwrap();
+            page.add_string("RocksDB is an LSM based storage engine")
+                .unwrap();
+            buffer.set_modified(10, 100);
         }
+
+        let page = file_manager
+            .read::<BufferPage>(&BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        assert_eq!(
+            Some("RocksDB is an LSM based storage engine"),
+            page.get_string(0).unwrap()
+        );
     }
 
     #[test]
-    fn test_buffer_pin_error_of_type_io_error() {
-        let io_error = io::Error::new(io::ErrorKind::Other, "disk failure");
-        let error = BufferPinError::IO(io_error);
+    fn keep_a_modified_buffer_in_memory_on_unpin_without_write_through() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
 
-        let formatted = format!("{}", error);
-        assert_eq!(formatted, "Buffer I/O error: disk failure");
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        {
+            let pinned = buffer_manager
+                .pin(BlockId::new(buffer_file_name, 0))
+                .unwrap();
+
+            let mut buffer = pinned.write();
+
+            let page = buffer.page().unwrap();
+            page.add_string("RocksDB is an LSM based storage engine")
+                .unwrap();
+            buffer.set_modified(10, 100);
+        }
+
+        let mut block = vec![0; BLOCK_SIZE];
+        file_manager
+            .read_into(&BlockId::new(buffer_file_name, 0), &mut block)
+            .unwrap();
+        assert!(block.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn pin_different_blocks_from_two_threads() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = Arc::new(BufferManager::new(2, &mut log_manager));
+        thread::scope(|scope| {
+            for block_number in 0..2 {
+                let buffer_manager = buffer_manager.clone();
+                scope.spawn(move || {
+                    let pinned = buffer_manager
+                        .pin(BlockId::new(buffer_file_name, block_number))
+                        .unwrap();
+                    let mut buffer = pinned.write();
+                    let page = buffer.page().unwrap();
+                    page.add_u32(block_number as u32 * 100).unwrap();
+                    buffer.set_modified(10, 0);
+                });
+            }
+        });
+        assert_eq!(2, buffer_manager.available_buffers());
+        assert!(buffer_manager.flush_all().is_ok());
+
+        for block_number in 0..2 {
+            let page = file_manager
+                .read::<BufferPage>(&BlockId::new(buffer_file_name, block_number))
+                .unwrap();
+            assert_eq!(Some(block_number as u32 * 100), page.get_u32(0).unwrap());
+        }
+    }
+
+    #[test]
+    fn pin_the_same_block_twice_in_one_thread() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(2, &mut log_manager);
+        let first = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        let second = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        assert_eq!(first.buffer_index, second.buffer_index);
+        assert_eq!(1, buffer_manager.available_buffers());
+
+        first.write().page().unwrap().add_u32(100).unwrap();
+        assert_eq!(
+            Some(100),
+            second.write().page().unwrap().get_u32(0).unwrap()
+        );
+
+        drop(first);
+        assert!(second.read().is_pinned());
+        assert_eq!(1, buffer_manager.available_buffers());
+
+        drop(second);
+        assert_eq!(2, buffer_manager.available_buffers());
     }
 
     #[test]
-    fn test_buffer_pin_error_of_type_io_unavailable_error() {
-        let error = BufferPinError::Unavailable;
+    fn flush_all_buffers_while_a_buffer_is_pinned() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let buffer_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", buffer_file_name);
 
-        let formatted = format!("{}", error);
-        assert_eq!(formatted, "Buffer is unavailable");
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(buffer_file_name).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        let pinned = buffer_manager
+            .pin(BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        {
+            let mut buffer = pinned.write();
+            buffer.page().unwrap().add_u32(100).unwrap();
+            buffer.set_modified(10, 0);
+        }
+        assert!(buffer_manager.flush_all().is_ok());
+
+        let page = file_manager
+            .read::<BufferPage>(&BlockId::new(buffer_file_name, 0))
+            .unwrap();
+        assert_eq!(Some(100), page.get_u32(0).unwrap());
+        assert!(pinned.read().is_pinned());
     }
 }
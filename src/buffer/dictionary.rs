@@ -0,0 +1,185 @@
+use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::EncoderDecoder;
+use crate::error::Error;
+use byteorder::ByteOrder;
+
+const RESERVED_SIZE_FOR_DICTIONARY_SIZE: usize = size_of::<u16>();
+
+#[derive(Clone)]
+pub(crate) struct Dictionary {
+    entries: Vec<String>,
+}
+
+impl Dictionary {
+    pub(crate) fn new() -> Self {
+        Dictionary { entries: vec![] }
+    }
+
+    /// Decodes the dictionary which ends at the end of the given buffer.
+    pub(crate) fn decode_from(buffer: &[u8]) -> Result<Self, Error> {
+        let corrupt = || Error::Corrupt("dictionary lies outside the page".to_string());
+        let end_of_entries = buffer
+            .len()
+            .checked_sub(RESERVED_SIZE_FOR_DICTIONARY_SIZE)
+            .ok_or_else(corrupt)?;
+        let size_of_entries = byteorder::LittleEndian::read_u16(&buffer[end_of_entries..]) as usize;
+        let start_of_entries = end_of_entries
+            .checked_sub(size_of_entries)
+            .ok_or_else(corrupt)?;
+
+        let entries_buffer = &buffer[start_of_entries..end_of_entries];
+        let mut dictionary = Dictionary::new();
+        let mut offset = 0;
+        while offset < entries_buffer.len() {
+            let length_prefix = entries_buffer
+                .get(offset..offset + size_of::<u16>())
+                .ok_or_else(corrupt)?;
+            let end_offset = offset
+                + size_of::<u16>()
+                + byteorder::LittleEndian::read_u16(length_prefix) as usize;
+            let entry = entries_buffer
+                .get(offset + size_of::<u16>()..end_offset)
+                .ok_or_else(corrupt)?;
+            let entry = std::str::from_utf8(entry)
+                .map_err(|_| Error::Corrupt("dictionary entry is not valid UTF-8".to_string()))?;
+            dictionary.entries.push(entry.to_string());
+            offset = end_offset;
+        }
+        Ok(dictionary)
+    }
+
+    /// The encoded size of the entries is stored in a u16, which also keeps every id within a
+    /// u16 since each entry takes at least two bytes.
+    pub(crate) fn id_for(&mut self, value: &str) -> Result<u16, Error> {
+        if let Some(id) = self.entries.iter().position(|entry| entry == value) {
+            return Ok(id as u16);
+        }
+        let size_of_entries =
+            self.size_of_entries() + StrEncoderDecoder.bytes_needed_for_encoding(value);
+        if size_of_entries > u16::MAX as usize {
+            return Err(Error::ValueTooLarge {
+                length: size_of_entries,
+                maximum: u16::MAX as usize,
+            });
+        }
+        self.entries.push(value.to_string());
+        Ok((self.entries.len() - 1) as u16)
+    }
+
+    /// Returns how much the encoded dictionary grows when `id_for` is called with the value.
+    pub(crate) fn bytes_needed_for(&self, value: &str) -> usize {
+        if self.entries.iter().any(|entry| entry == value) {
+            return 0;
+        }
+        let bytes_needed = StrEncoderDecoder.bytes_needed_for_encoding(value);
+        if self.is_empty() {
+            return bytes_needed + RESERVED_SIZE_FOR_DICTIONARY_SIZE;
+        }
+        bytes_needed
+    }
+
+    pub(crate) fn get(&self, id: u16) -> Option<&str> {
+        self.entries.get(id as usize).map(String::as_str)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let size_of_entries = self.size_of_entries();
+        let mut encoded = vec![0; size_of_entries + RESERVED_SIZE_FOR_DICTIONARY_SIZE];
+        let mut offset = 0;
+        for entry in &self.entries {
+            offset += StrEncoderDecoder.encode(entry, &mut encoded, offset);
+        }
+        byteorder::LittleEndian::write_u16(&mut encoded[offset..], size_of_entries as u16);
+        encoded
+    }
+
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        self.size_of_entries() + RESERVED_SIZE_FOR_DICTIONARY_SIZE
+    }
+
+    fn size_of_entries(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| StrEncoderDecoder.bytes_needed_for_encoding(entry))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::dictionary::Dictionary;
+    use crate::error::Error;
+
+    #[test]
+    fn reuse_the_id_of_an_existing_entry() {
+        let mut dictionary = Dictionary::new();
+
+        assert_eq!(0, dictionary.id_for("RocksDB").unwrap());
+        assert_eq!(1, dictionary.id_for("BoltDB").unwrap());
+        assert_eq!(0, dictionary.id_for("RocksDB").unwrap());
+        assert_eq!(Some("BoltDB"), dictionary.get(1));
+        assert_eq!(None, dictionary.get(2));
+    }
+
+    #[test]
+    fn encode_and_decode_a_dictionary() {
+        let mut dictionary = Dictionary::new();
+        dictionary.id_for("RocksDB").unwrap();
+        dictionary.id_for("BoltDB").unwrap();
+
+        let mut buffer = vec![0xFF; 10];
+        buffer.extend(dictionary.encode());
+        assert_eq!(dictionary.size_in_bytes() + 10, buffer.len());
+
+        let decoded = Dictionary::decode_from(&buffer).unwrap();
+        assert_eq!(Some("RocksDB"), decoded.get(0));
+        assert_eq!(Some("BoltDB"), decoded.get(1));
+        assert_eq!(dictionary.size_in_bytes(), decoded.size_in_bytes());
+    }
+
+    #[test]
+    fn attempt_to_decode_a_dictionary_larger_than_the_buffer() {
+        let buffer = [1, 2, 3, 100, 0];
+
+        assert!(matches!(
+            Dictionary::decode_from(&buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_add_an_entry_beyond_the_encodable_size_of_the_dictionary() {
+        let mut dictionary = Dictionary::new();
+        let entry = "R".repeat(u16::MAX as usize / 2);
+        assert_eq!(0, dictionary.id_for(&entry).unwrap());
+
+        let another_entry = "B".repeat(u16::MAX as usize / 2);
+        assert!(matches!(
+            dictionary.id_for(&another_entry),
+            Err(Error::ValueTooLarge { .. })
+        ));
+        assert_eq!(0, dictionary.id_for(&entry).unwrap());
+        assert_eq!(None, dictionary.get(1));
+    }
+
+    #[test]
+    fn bytes_needed_for_new_and_existing_entries() {
+        let mut dictionary = Dictionary::new();
+        assert_eq!(11, dictionary.bytes_needed_for("RocksDB"));
+
+        dictionary.id_for("RocksDB").unwrap();
+        assert_eq!(0, dictionary.bytes_needed_for("RocksDB"));
+        assert_eq!(8, dictionary.bytes_needed_for("BoltDB"));
+
+        let size_in_bytes = dictionary.size_in_bytes();
+        dictionary.id_for("BoltDB").unwrap();
+        assert_eq!(size_in_bytes + 8, dictionary.size_in_bytes());
+    }
+}
@@ -1,11 +1,19 @@
+use crate::buffer::overflow::OverflowPointer;
 use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::char_encoder_decoder::CharEncoderDecoder;
+use crate::encodex::fixed16_encoder_decoder::Fixed16EncoderDecoder;
 use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::timestamp_encoder_decoder::TimestampEncoderDecoder;
 use crate::encodex::U8EncoderDecoder;
 use crate::encodex::{EncoderDecoder, EndOffset};
 use crate::encodex::{U16EncoderDecoder, U32EncoderDecoder};
+use crate::error::Error;
+use byteorder::ByteOrder;
+use std::time::SystemTime;
 
 const RESERVED_SIZE_FOR_TYPE: usize = size_of::<u8>();
 
+#[derive(Clone)]
 pub(crate) struct Fields {
     types: Vec<FieldType>,
 }
@@ -19,17 +27,47 @@ pub(crate) enum FieldType {
     TypeU32,
     TypeBytes,
     TypeString,
+    TypeOverflow,
+    TypeNull,
+    TypeTimestamp,
+    TypeUuid,
+    TypeDictStr,
+    TypeChar,
 }
 
-impl From<u8> for FieldType {
-    fn from(value: u8) -> Self {
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum FieldValue {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    Bytes(Vec<u8>),
+    Str(String),
+    Overflow(OverflowPointer),
+    Null,
+    Timestamp(SystemTime),
+    Uuid([u8; 16]),
+    DictStr(String),
+    Char(char),
+}
+
+impl TryFrom<u8> for FieldType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => FieldType::TypeU8,
-            1 => FieldType::TypeU16,
-            2 => FieldType::TypeU32,
-            3 => FieldType::TypeBytes,
-            4 => FieldType::TypeString,
-            _ => unreachable!(),
+            0 => Ok(FieldType::TypeU8),
+            1 => Ok(FieldType::TypeU16),
+            2 => Ok(FieldType::TypeU32),
+            3 => Ok(FieldType::TypeBytes),
+            4 => Ok(FieldType::TypeString),
+            5 => Ok(FieldType::TypeOverflow),
+            6 => Ok(FieldType::TypeNull),
+            7 => Ok(FieldType::TypeTimestamp),
+            8 => Ok(FieldType::TypeUuid),
+            9 => Ok(FieldType::TypeDictStr),
+            10 => Ok(FieldType::TypeChar),
+            _ => Err(Error::Decode(format!("unknown field type {}", value))),
         }
     }
 }
@@ -42,11 +80,38 @@ impl From<FieldType> for u8 {
             FieldType::TypeU32 => 2,
             FieldType::TypeBytes => 3,
             FieldType::TypeString => 4,
+            FieldType::TypeOverflow => 5,
+            FieldType::TypeNull => 6,
+            FieldType::TypeTimestamp => 7,
+            FieldType::TypeUuid => 8,
+            FieldType::TypeDictStr => 9,
+            FieldType::TypeChar => 10,
         }
     }
 }
 
 impl FieldType {
+    /// Bytes a field of this type takes besides its variable-length payload, which is the
+    /// content of bytes and strings, and the file name of an overflow pointer.
+    pub(crate) fn reserved_size_in_bytes(&self) -> usize {
+        match self {
+            FieldType::TypeU8 => size_of::<u8>(),
+            FieldType::TypeU16 => size_of::<u16>(),
+            FieldType::TypeU32 => size_of::<u32>(),
+            FieldType::TypeBytes | FieldType::TypeString => {
+                BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE
+            }
+            FieldType::TypeOverflow => {
+                BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE + 2 * size_of::<u32>()
+            }
+            FieldType::TypeNull => 0,
+            FieldType::TypeTimestamp => size_of::<i64>(),
+            FieldType::TypeUuid => Fixed16EncoderDecoder::SIZE,
+            FieldType::TypeDictStr => size_of::<u16>(),
+            FieldType::TypeChar => CharEncoderDecoder::SIZE,
+        }
+    }
+
     pub(crate) fn end_offset_post_decode(&self, buffer: &[u8], from_offset: usize) -> EndOffset {
         match self {
             FieldType::TypeU8 => U8EncoderDecoder.decode(buffer, from_offset).1,
@@ -54,8 +119,59 @@ impl FieldType {
             FieldType::TypeU32 => U32EncoderDecoder.decode(buffer, from_offset).1,
             FieldType::TypeBytes => BytesEncoderDecoder.decode(buffer, from_offset).1,
             FieldType::TypeString => StrEncoderDecoder.decode(buffer, from_offset).1,
+            FieldType::TypeOverflow => OverflowPointer::decode(buffer, from_offset).1,
+            FieldType::TypeNull => from_offset,
+            FieldType::TypeTimestamp => TimestampEncoderDecoder.decode(buffer, from_offset).1,
+            FieldType::TypeUuid => Fixed16EncoderDecoder.decode(buffer, from_offset).1,
+            FieldType::TypeDictStr => U16EncoderDecoder.decode(buffer, from_offset).1,
+            FieldType::TypeChar => from_offset + CharEncoderDecoder::SIZE,
         }
     }
+
+    pub(crate) fn checked_end_offset(
+        &self,
+        buffer: &[u8],
+        from_offset: usize,
+    ) -> Option<EndOffset> {
+        let end_offset = match self {
+            FieldType::TypeU8 => from_offset + size_of::<u8>(),
+            FieldType::TypeU16 => from_offset + size_of::<u16>(),
+            FieldType::TypeU32 => from_offset + size_of::<u32>(),
+            FieldType::TypeBytes => checked_end_offset_of_byte_slice(buffer, from_offset)?,
+            FieldType::TypeString => checked_end_offset_of_str(buffer, from_offset)?,
+            FieldType::TypeOverflow => {
+                checked_end_offset_of_str(buffer, from_offset)? + 2 * size_of::<u32>()
+            }
+            FieldType::TypeNull => from_offset,
+            FieldType::TypeTimestamp => from_offset + size_of::<i64>(),
+            FieldType::TypeUuid => from_offset + Fixed16EncoderDecoder::SIZE,
+            FieldType::TypeDictStr => from_offset + size_of::<u16>(),
+            FieldType::TypeChar => checked_end_offset_of_char(buffer, from_offset)?,
+        };
+        (end_offset <= buffer.len()).then_some(end_offset)
+    }
+}
+
+fn checked_end_offset_of_byte_slice(buffer: &[u8], from_offset: usize) -> Option<EndOffset> {
+    let start_of_slice = from_offset + BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE;
+    let slice_length = byteorder::LittleEndian::read_u16(buffer.get(from_offset..start_of_slice)?);
+    let end_offset = start_of_slice + slice_length as usize;
+    (end_offset <= buffer.len()).then_some(end_offset)
+}
+
+fn checked_end_offset_of_char(buffer: &[u8], from_offset: usize) -> Option<EndOffset> {
+    buffer.get(from_offset..from_offset + CharEncoderDecoder::SIZE)?;
+    CharEncoderDecoder
+        .decode(buffer, from_offset)
+        .ok()
+        .map(|(_, end_offset)| end_offset)
+}
+
+fn checked_end_offset_of_str(buffer: &[u8], from_offset: usize) -> Option<EndOffset> {
+    let end_offset = checked_end_offset_of_byte_slice(buffer, from_offset)?;
+    let start_of_str = from_offset + BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE;
+    std::str::from_utf8(&buffer[start_of_str..end_offset]).ok()?;
+    Some(end_offset)
 }
 
 impl Fields {
@@ -63,12 +179,12 @@ impl Fields {
         Fields { types: vec![] }
     }
 
-    pub(crate) fn decode_from(bytes: &[u8]) -> Fields {
+    pub(crate) fn decode_from(bytes: &[u8]) -> Result<Fields, Error> {
         let mut types = Fields::new();
         for description in bytes {
-            types.add(FieldType::from(*description));
+            types.add(FieldType::try_from(*description)?);
         }
-        types
+        Ok(types)
     }
 
     pub(crate) fn add(&mut self, field_type: FieldType) {
@@ -83,10 +199,29 @@ impl Fields {
         buffer
     }
 
+    pub(crate) fn insert(&mut self, index: usize, field_type: FieldType) {
+        self.types.insert(index, field_type);
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) -> Option<FieldType> {
+        if index >= self.types.len() {
+            return None;
+        }
+        Some(self.types.remove(index))
+    }
+
     pub(crate) fn type_at(&self, index: usize) -> Option<&FieldType> {
         self.types.get(index)
     }
 
+    pub(crate) fn contains(&self, field_type: FieldType) -> bool {
+        self.types.contains(&field_type)
+    }
+
+    pub(crate) fn as_slice(&self) -> &[FieldType] {
+        &self.types
+    }
+
     pub(crate) fn last(&self) -> Option<&FieldType> {
         self.types.last()
     }
@@ -107,6 +242,7 @@ impl Fields {
 #[cfg(test)]
 mod fields_tests {
     use crate::buffer::field_types::{FieldType, Fields};
+    use crate::error::Error;
 
     #[test]
     fn encode_and_decode_types_with_a_single_field() {
@@ -114,7 +250,7 @@ mod fields_tests {
         types.add(FieldType::TypeU8);
 
         let encoded = types.encode();
-        let decoded = Fields::decode_from(&encoded);
+        let decoded = Fields::decode_from(&encoded).unwrap();
 
         assert_eq!(&FieldType::TypeU8, decoded.type_at(0).unwrap());
     }
@@ -126,7 +262,7 @@ mod fields_tests {
         types.add(FieldType::TypeBytes);
 
         let encoded = types.encode();
-        let decoded = Fields::decode_from(&encoded);
+        let decoded = Fields::decode_from(&encoded).unwrap();
 
         assert_eq!(&FieldType::TypeU8, decoded.type_at(0).unwrap());
         assert_eq!(&FieldType::TypeBytes, decoded.type_at(1).unwrap());
@@ -142,7 +278,7 @@ mod fields_tests {
         types.add(FieldType::TypeU32);
 
         let encoded = types.encode();
-        let decoded = Fields::decode_from(&encoded);
+        let decoded = Fields::decode_from(&encoded).unwrap();
 
         assert_eq!(&FieldType::TypeU8, decoded.type_at(0).unwrap());
         assert_eq!(&FieldType::TypeBytes, decoded.type_at(1).unwrap());
@@ -151,6 +287,25 @@ mod fields_tests {
         assert_eq!(&FieldType::TypeU32, decoded.type_at(4).unwrap());
     }
 
+    #[test]
+    fn every_encoded_type_decodes_to_the_same_type() {
+        (0..=u8::MAX)
+            .filter_map(|code| FieldType::try_from(code).ok())
+            .for_each(|field_type| {
+                let code: u8 = field_type.into();
+                assert_eq!(field_type, FieldType::try_from(code).unwrap());
+            });
+        assert!(FieldType::try_from(u8::from(FieldType::TypeU32)).is_ok());
+    }
+
+    #[test]
+    fn attempt_to_decode_an_unknown_type() {
+        assert!(matches!(
+            Fields::decode_from(&[0, 42]),
+            Err(Error::Decode(_))
+        ));
+    }
+
     #[test]
     fn get_type_at_an_index() {
         let mut types = Fields::new();
@@ -159,6 +314,17 @@ mod fields_tests {
         assert_eq!(&FieldType::TypeU8, types.type_at(0).unwrap());
     }
 
+    #[test]
+    fn remove_a_type() {
+        let mut types = Fields::new();
+        types.add(FieldType::TypeU8);
+        types.add(FieldType::TypeString);
+
+        assert_eq!(Some(FieldType::TypeU8), types.remove(0));
+        assert_eq!(&FieldType::TypeString, types.type_at(0).unwrap());
+        assert_eq!(None, types.remove(1));
+    }
+
     #[test]
     fn length_of_types() {
         let mut types = Fields::new();
@@ -208,6 +374,52 @@ mod field_type_tests {
         assert!(FieldType::TypeBytes.end_offset_post_decode(&buffer, 10) > 16);
     }
 
+    #[test]
+    fn checked_end_offset_for_fields_within_the_buffer() {
+        let mut buffer = vec![0; 100];
+        let _ = StrEncoderDecoder.encode("Rocksdb", &mut buffer, 10);
+
+        assert_eq!(
+            Some(19),
+            FieldType::TypeString.checked_end_offset(&buffer, 10)
+        );
+        assert_eq!(
+            Some(100),
+            FieldType::TypeU32.checked_end_offset(&buffer, 96)
+        );
+    }
+
+    #[test]
+    fn checked_end_offset_for_fields_beyond_the_buffer() {
+        let mut buffer = vec![0; 100];
+        byteorder::LittleEndian::write_u16(&mut buffer[90..92], 200);
+
+        assert_eq!(None, FieldType::TypeBytes.checked_end_offset(&buffer, 90));
+        assert_eq!(None, FieldType::TypeBytes.checked_end_offset(&buffer, 99));
+        assert_eq!(None, FieldType::TypeU32.checked_end_offset(&buffer, 97));
+    }
+
+    #[test]
+    fn checked_end_offset_for_a_string_with_invalid_utf8() {
+        let mut buffer = vec![0; 100];
+        let _ = BytesEncoderDecoder.encode(&[0xFF, 0xFE], &mut buffer, 10);
+
+        assert_eq!(None, FieldType::TypeString.checked_end_offset(&buffer, 10));
+        assert_eq!(
+            Some(14),
+            FieldType::TypeBytes.checked_end_offset(&buffer, 10)
+        );
+    }
+
+    #[test]
+    fn checked_end_offset_for_an_invalid_char() {
+        let mut buffer = vec![0; 100];
+        byteorder::LittleEndian::write_u32(&mut buffer[10..14], 0xD800);
+
+        assert_eq!(None, FieldType::TypeChar.checked_end_offset(&buffer, 10));
+        assert_eq!(Some(14), FieldType::TypeU32.checked_end_offset(&buffer, 10));
+    }
+
     #[test]
     fn end_offset_post_decode_for_string() {
         let mut buffer = vec![0; 100];
@@ -0,0 +1,86 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+const BYTES_PER_LINE: usize = 16;
+
+pub(crate) struct Region {
+    pub(crate) range: Range<usize>,
+    pub(crate) label: String,
+}
+
+pub(crate) fn hex_dump(bytes: &[u8], regions: &[Region]) -> String {
+    let mut dump = String::new();
+    for (line_index, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let line_offset = line_index * BYTES_PER_LINE;
+        let _ = write!(dump, "{:08x}  ", line_offset);
+        for column in 0..BYTES_PER_LINE {
+            match line.get(column) {
+                Some(byte) => {
+                    let _ = write!(dump, "{:02x} ", byte);
+                }
+                None => dump.push_str("   "),
+            }
+        }
+        dump.push('|');
+        line.iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                }
+            })
+            .for_each(|character| dump.push(character));
+        dump.push('|');
+
+        let line_range = line_offset..line_offset + line.len();
+        let labels: Vec<&str> = regions
+            .iter()
+            .filter(|region| {
+                region.range.start < line_range.end && line_range.start < region.range.end
+            })
+            .map(|region| region.label.as_str())
+            .collect();
+        if !labels.is_empty() {
+            let _ = write!(dump, "  <- {}", labels.join(", "));
+        }
+        dump.push('\n');
+    }
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::hex_dump::{hex_dump, Region};
+
+    #[test]
+    fn dump_bytes_with_a_partial_last_line() {
+        let bytes: Vec<u8> = (0..20).collect();
+        let dump = hex_dump(&bytes, &[]);
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(2, lines.len());
+        assert!(lines[0].starts_with("00000000  00 01 02"));
+        assert!(lines[1].starts_with("00000010  10 11 12 13 "));
+    }
+
+    #[test]
+    fn dump_printable_bytes_as_ascii() {
+        let dump = hex_dump(b"RocksDB\n", &[]);
+        assert!(dump.contains("|RocksDB.|"));
+    }
+
+    #[test]
+    fn dump_bytes_with_labelled_regions() {
+        let bytes = vec![0; 32];
+        let regions = vec![Region {
+            range: 30..32,
+            label: "footer".to_string(),
+        }];
+        let dump = hex_dump(&bytes, &regions);
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert!(!lines[0].contains("footer"));
+        assert!(lines[1].ends_with("<- footer"));
+    }
+}
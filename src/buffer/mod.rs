@@ -1,12 +1,18 @@
-mod buffer_manager;
-mod field_types;
+pub(crate) mod buffer_manager;
+mod dictionary;
+pub(crate) mod field_types;
+mod hex_dump;
+pub(crate) mod overflow;
 pub(crate) mod page;
 pub(crate) mod page_encoder_decoder;
+pub(crate) mod page_ref;
+#[cfg(feature = "serde")]
+mod page_serde;
 
 use crate::buffer::page::BufferPage;
+use crate::error::Error;
 use crate::file::block_id::BlockId;
 use crate::log::log_manager::LogManager;
-use std::io;
 use std::path::Path;
 
 pub(crate) struct Buffer {
@@ -32,7 +38,7 @@ impl Buffer {
         &mut self,
         block_id: BlockId,
         log_manager: &mut LogManager<PathType>,
-    ) -> Result<(), io::Error> {
+    ) -> Result<(), Error> {
         self.flush(log_manager)?;
         self.page = Some(log_manager.file_manager().read::<BufferPage>(&block_id)?);
         self.block_id = Some(block_id);
@@ -45,8 +51,8 @@ impl Buffer {
         self.log_sequence_number = log_sequence_number;
     }
 
-    pub(crate) fn has_block_id(&self, block_id: &BlockId) -> bool {
-        self.block_id.as_ref() == Some(block_id)
+    pub(crate) fn block_id(&self) -> Option<&BlockId> {
+        self.block_id.as_ref()
     }
 
     pub(crate) fn pin(&mut self) {
@@ -64,7 +70,7 @@ impl Buffer {
     pub(crate) fn flush<PathType: AsRef<Path>>(
         &mut self,
         log_manager: &mut LogManager<PathType>,
-    ) -> Result<(), io::Error> {
+    ) -> Result<(), Error> {
         if self.transaction_number >= 0 && self.page.is_some() {
             log_manager.flush(self.log_sequence_number)?;
             log_manager.file_manager().write(
@@ -76,7 +82,6 @@ impl Buffer {
         Ok(())
     }
 
-    #[cfg(test)]
     pub(crate) fn page(&mut self) -> Option<&mut BufferPage> {
         self.page.as_mut()
     }
@@ -110,8 +115,9 @@ mod tests {
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u16(250);
-        page.add_string("BoltDB is a B+Tree based storage engine");
+        page.add_u16(250).unwrap();
+        page.add_string("BoltDB is a B+Tree based storage engine")
+            .unwrap();
 
         assert!(log_manager
             .file_manager()
@@ -124,10 +130,10 @@ mod tests {
             .unwrap();
 
         let buffer_page = buffer.page.unwrap();
-        assert_eq!(250, buffer_page.get_u16(0).unwrap());
+        assert_eq!(Some(250), buffer_page.get_u16(0).unwrap());
         assert_eq!(
             Some("BoltDB is a B+Tree based storage engine"),
-            buffer_page.get_string(1)
+            buffer_page.get_string(1).unwrap()
         );
     }
 
@@ -142,8 +148,9 @@ mod tests {
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u16(250);
-        page.add_string("BoltDB is a B+Tree based storage engine");
+        page.add_u16(250).unwrap();
+        page.add_string("BoltDB is a B+Tree based storage engine")
+            .unwrap();
 
         assert!(log_manager
             .file_manager()
@@ -172,8 +179,9 @@ mod tests {
         let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
 
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u16(250);
-        page.add_string("BoltDB is a B+Tree based storage engine");
+        page.add_u16(250).unwrap();
+        page.add_string("BoltDB is a B+Tree based storage engine")
+            .unwrap();
 
         assert!(log_manager
             .file_manager()
@@ -213,8 +221,9 @@ mod tests {
             .unwrap();
 
         let page = buffer.page.as_mut().unwrap();
-        page.add_u16(250);
-        page.add_string("BoltDB is a B+Tree based storage engine");
+        page.add_u16(250).unwrap();
+        page.add_string("BoltDB is a B+Tree based storage engine")
+            .unwrap();
 
         let any_transaction_number = 10;
         let any_log_sequence_number = 100;
@@ -226,10 +235,10 @@ mod tests {
             .unwrap();
 
         let reassigned_buffer_page = buffer.page.unwrap();
-        assert_eq!(250, reassigned_buffer_page.get_u16(0).unwrap());
+        assert_eq!(Some(250), reassigned_buffer_page.get_u16(0).unwrap());
         assert_eq!(
             Some("BoltDB is a B+Tree based storage engine"),
-            reassigned_buffer_page.get_string(1)
+            reassigned_buffer_page.get_string(1).unwrap()
         );
     }
 }
@@ -0,0 +1,133 @@
+use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::{BytesNeededForEncoding, EncoderDecoder, EndOffset, U32EncoderDecoder};
+use crate::error::Error;
+use crate::file::block_id::BlockId;
+use crate::file::file_manager::FileManager;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct OverflowPointer {
+    pub(crate) block_id: BlockId,
+    pub(crate) length: u32,
+}
+
+impl OverflowPointer {
+    pub(crate) fn spill<PathType: AsRef<Path>>(
+        value: &[u8],
+        file_manager: &FileManager<PathType>,
+        overflow_file_name: &str,
+    ) -> Result<OverflowPointer, Error> {
+        let length = u32::try_from(value.len()).map_err(|_| Error::ValueTooLarge {
+            length: value.len(),
+            maximum: u32::MAX as usize,
+        })?;
+        let mut first_block_id = None;
+        for chunk in value.chunks(file_manager.block_size) {
+            let block_id = file_manager.append_empty_block(overflow_file_name)?;
+            file_manager.write(&block_id, chunk)?;
+            first_block_id.get_or_insert(block_id);
+        }
+        let block_id = match first_block_id {
+            Some(block_id) => block_id,
+            None => BlockId::new(
+                overflow_file_name,
+                file_manager.number_of_blocks(overflow_file_name)?,
+            ),
+        };
+        Ok(OverflowPointer { block_id, length })
+    }
+
+    pub(crate) fn reassemble<PathType: AsRef<Path>>(
+        &self,
+        file_manager: &FileManager<PathType>,
+    ) -> Result<Vec<u8>, Error> {
+        let mut value = vec![0; self.length as usize];
+        let mut block_id = self.block_id.clone();
+        for chunk in value.chunks_mut(file_manager.block_size) {
+            file_manager.read_into(&block_id, chunk)?;
+            block_id = block_id.next();
+        }
+        Ok(value)
+    }
+
+    pub(crate) fn bytes_needed_for_encoding(&self) -> BytesNeededForEncoding {
+        Self::bytes_needed_for(self.block_id.file_name())
+    }
+
+    /// The block number and the length are fixed size, so the size of a pointer is known
+    /// before the value is spilled.
+    pub(crate) fn bytes_needed_for(overflow_file_name: &str) -> BytesNeededForEncoding {
+        StrEncoderDecoder.bytes_needed_for_encoding(overflow_file_name) + 2 * size_of::<u32>()
+    }
+
+    pub(crate) fn encode(
+        &self,
+        destination: &mut [u8],
+        destination_starting_offset: usize,
+    ) -> BytesNeededForEncoding {
+        let block_number = self.block_id.block_number as u32;
+        let mut offset = destination_starting_offset;
+        offset += StrEncoderDecoder.encode(self.block_id.file_name(), destination, offset);
+        offset += U32EncoderDecoder.encode(&block_number, destination, offset);
+        offset += U32EncoderDecoder.encode(&self.length, destination, offset);
+        offset - destination_starting_offset
+    }
+
+    pub(crate) fn decode(encoded: &[u8], from_offset: usize) -> (OverflowPointer, EndOffset) {
+        let (file_name, offset) = StrEncoderDecoder.decode(encoded, from_offset);
+        let (block_number, offset) = U32EncoderDecoder.decode(encoded, offset);
+        let (length, end_offset) = U32EncoderDecoder.decode(encoded, offset);
+        (
+            OverflowPointer {
+                block_id: BlockId::new(&file_name, *block_number as usize),
+                length: *length,
+            },
+            end_offset,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::overflow::OverflowPointer;
+    use crate::file::block_id::BlockId;
+    use crate::file::file_manager::FileManager;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE: usize = 64;
+
+    #[test]
+    fn encode_decode_an_overflow_pointer() {
+        let pointer = OverflowPointer {
+            block_id: BlockId::new("overflow.db", 3),
+            length: 500,
+        };
+        let mut buffer = vec![0; 100];
+        let bytes_needed = pointer.encode(&mut buffer, 10);
+
+        assert_eq!(pointer.bytes_needed_for_encoding(), bytes_needed);
+        assert_eq!(
+            OverflowPointer::bytes_needed_for("overflow.db"),
+            bytes_needed
+        );
+        assert_eq!(
+            (pointer, 10 + bytes_needed),
+            OverflowPointer::decode(&buffer, 10)
+        );
+    }
+
+    #[test]
+    fn spill_and_reassemble_a_value() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let value: Vec<u8> = (0..BLOCK_SIZE * 2 + 10).map(|byte| byte as u8).collect();
+
+        let pointer = OverflowPointer::spill(&value, &file_manager, file_name).unwrap();
+        assert_eq!(3, file_manager.number_of_blocks(file_name).unwrap());
+        assert_eq!(value, pointer.reassemble(&file_manager).unwrap());
+    }
+}
@@ -1,157 +1,655 @@
 use crate::assert_borrowed_type;
-use crate::buffer::field_types::{FieldType, Fields};
-use crate::buffer::page_encoder_decoder::{PageDecoder, PageEncoder};
+use crate::buffer::dictionary::Dictionary;
+use crate::buffer::field_types::{FieldType, FieldValue, Fields};
+use crate::buffer::hex_dump::{hex_dump, Region};
+use crate::buffer::overflow::OverflowPointer;
+use crate::buffer::page_encoder_decoder::{
+    PageDecoder, PageEncoder, RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS,
+};
 use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::char_encoder_decoder::CharEncoderDecoder;
+use crate::encodex::fixed16_encoder_decoder::Fixed16EncoderDecoder;
 use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::timestamp_encoder_decoder::TimestampEncoderDecoder;
 use crate::encodex::U8EncoderDecoder;
 use crate::encodex::{BytesNeededForEncoding, EncoderDecoder};
 use crate::encodex::{U16EncoderDecoder, U32EncoderDecoder};
+use crate::error::Error;
+use crate::file::file_manager::FileManager;
 use crate::file::starting_offsets::StartingOffsets;
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::SystemTime;
 
+#[derive(Clone)]
 pub(crate) struct BufferPage {
     pub(crate) buffer: Vec<u8>,
     pub(crate) starting_offsets: StartingOffsets,
     pub(crate) types: Fields,
+    pub(crate) dictionary: Dictionary,
     pub(crate) current_write_offset: usize,
 }
 
 impl crate::page::Page for BufferPage {
-    fn decode_from(buffer: Vec<u8>) -> Self {
+    fn decode_from(buffer: Vec<u8>) -> Result<Self, Error> {
         if buffer.is_empty() {
-            panic!("buffer cannot be empty while decoding the page");
+            return Err(Error::Decode(
+                "buffer cannot be empty while decoding the page".to_string(),
+            ));
         }
         PageDecoder::decode_page(buffer)
     }
+
+    fn decode_verified(buffer: Vec<u8>) -> Result<Self, Error> {
+        PageDecoder::try_decode_page(buffer)
+    }
+
+    fn encode(&mut self) -> &[u8] {
+        self.encode()
+    }
+}
+
+impl PartialEq for BufferPage {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter_fields().eq(other.iter_fields())
+    }
 }
 
-//TODO: Support for deletion
 impl BufferPage {
     pub(crate) fn new(block_size: usize) -> Self {
         BufferPage {
             buffer: vec![0; block_size],
             starting_offsets: StartingOffsets::new(),
             types: Fields::new(),
+            dictionary: Dictionary::new(),
             current_write_offset: 0,
         }
     }
 
-    pub(crate) fn add_bytes(&mut self, value: Vec<u8>) {
+    pub(crate) fn add_bytes(&mut self, value: Vec<u8>) -> Result<(), Error> {
+        Self::ensure_encodable_length(value.len())?;
         self.add_field(
             |destination, current_write_offset| {
                 BytesEncoderDecoder.encode(&value, destination, current_write_offset)
             },
+            BytesEncoderDecoder.bytes_needed_for_encoding(&value),
             FieldType::TypeBytes,
         )
     }
 
-    //TODO: What if the new value does not match the old size
-    pub(crate) fn mutate_bytes(&mut self, value: Vec<u8>, index: usize) {
-        self.assert_field_type(index, FieldType::TypeBytes);
+    pub(crate) fn add_bytes_or_overflow<PathType: AsRef<Path>>(
+        &mut self,
+        value: Vec<u8>,
+        file_manager: &FileManager<PathType>,
+        overflow_file_name: &str,
+    ) -> Result<(), Error> {
+        if value.len() <= BytesEncoderDecoder::MAX_LENGTH
+            && BytesEncoderDecoder.bytes_needed_for_encoding(&value) <= self.free_space()
+        {
+            return self.add_bytes(value);
+        }
+        if OverflowPointer::bytes_needed_for(overflow_file_name) > self.free_space() {
+            return Err(Error::PageFull);
+        }
+        let pointer = OverflowPointer::spill(&value, file_manager, overflow_file_name)?;
+        self.add_field(
+            |destination, current_write_offset| pointer.encode(destination, current_write_offset),
+            pointer.bytes_needed_for_encoding(),
+            FieldType::TypeOverflow,
+        )
+    }
+
+    pub(crate) fn mutate_bytes(&mut self, value: Vec<u8>, index: usize) -> Result<(), Error> {
+        self.ensure_field_type(index, FieldType::TypeBytes)?;
+        Self::ensure_encodable_length(value.len())?;
         self.mutate_field(
             |destination, current_write_offset| {
                 BytesEncoderDecoder.encode(&value, destination, current_write_offset)
             },
+            BytesEncoderDecoder.bytes_needed_for_encoding(&value),
             index,
-        );
+        )
+    }
+
+    pub(crate) fn insert_bytes_at(&mut self, value: Vec<u8>, index: usize) -> Result<(), Error> {
+        Self::ensure_encodable_length(value.len())?;
+        self.insert_field(
+            |destination, starting_offset| {
+                BytesEncoderDecoder.encode(&value, destination, starting_offset)
+            },
+            BytesEncoderDecoder.bytes_needed_for_encoding(&value),
+            FieldType::TypeBytes,
+            index,
+        )
     }
 
-    pub(crate) fn add_string(&mut self, value: &str) {
+    pub(crate) fn add_string(&mut self, value: &str) -> Result<(), Error> {
+        Self::ensure_encodable_length(value.len())?;
         self.add_field(
             |destination, current_write_offset| {
                 StrEncoderDecoder.encode(value, destination, current_write_offset)
             },
+            StrEncoderDecoder.bytes_needed_for_encoding(value),
             FieldType::TypeString,
         )
     }
 
-    //TODO: What if the new value does not match the old size
-    pub(crate) fn mutate_string(&mut self, value: &str, index: usize) {
-        self.assert_field_type(index, FieldType::TypeString);
+    pub(crate) fn mutate_string(&mut self, value: &str, index: usize) -> Result<(), Error> {
+        self.ensure_field_type(index, FieldType::TypeString)?;
+        Self::ensure_encodable_length(value.len())?;
         self.mutate_field(
             |destination, current_write_offset| {
                 StrEncoderDecoder.encode(value, destination, current_write_offset)
             },
+            StrEncoderDecoder.bytes_needed_for_encoding(value),
             index,
-        );
+        )
+    }
+
+    pub(crate) fn insert_string_at(&mut self, value: &str, index: usize) -> Result<(), Error> {
+        Self::ensure_encodable_length(value.len())?;
+        self.insert_field(
+            |destination, starting_offset| {
+                StrEncoderDecoder.encode(value, destination, starting_offset)
+            },
+            StrEncoderDecoder.bytes_needed_for_encoding(value),
+            FieldType::TypeString,
+            index,
+        )
+    }
+
+    pub(crate) fn add_null(&mut self) -> Result<(), Error> {
+        self.add_field(|_, _| 0, 0, FieldType::TypeNull)
+    }
+
+    pub(crate) fn is_null(&self, index: usize) -> bool {
+        self.types.type_at(index) == Some(&FieldType::TypeNull)
     }
 
-    pub(crate) fn get_bytes(&self, index: usize) -> Option<&[u8]> {
-        self.assert_field_type(index, FieldType::TypeBytes);
+    pub(crate) fn get_bytes(&self, index: usize) -> Result<Option<&[u8]>, Error> {
+        if self.is_null(index) {
+            return Ok(None);
+        }
+        self.ensure_field_type(index, FieldType::TypeBytes)?;
         let buffer = self.get(
             |starting_offset| BytesEncoderDecoder.decode(&self.buffer, starting_offset).0,
             index,
-        )?;
-        Some(assert_borrowed_type(buffer))
+        );
+        Ok(buffer.map(assert_borrowed_type))
     }
 
-    pub(crate) fn get_string(&self, index: usize) -> Option<&str> {
-        self.assert_field_type(index, FieldType::TypeString);
-        let str = self.get(
-            |starting_offset| StrEncoderDecoder.decode(&self.buffer, starting_offset).0,
+    pub(crate) fn get_bytes_or_overflow<PathType: AsRef<Path>>(
+        &self,
+        index: usize,
+        file_manager: &FileManager<PathType>,
+    ) -> Result<Option<Cow<'_, [u8]>>, Error> {
+        if self.types.type_at(index) != Some(&FieldType::TypeOverflow) {
+            return Ok(self.get_bytes(index)?.map(Cow::Borrowed));
+        }
+        let pointer = self.get(
+            |starting_offset| OverflowPointer::decode(&self.buffer, starting_offset).0,
             index,
-        )?;
-        Some(assert_borrowed_type(str))
+        );
+        match pointer {
+            Some(pointer) => Ok(Some(Cow::Owned(pointer.reassemble(file_manager)?))),
+            None => Ok(None),
+        }
     }
 
-    pub(crate) fn encode(&mut self) -> &[u8] {
-        if self.starting_offsets.length() == 0 {
-            panic!("empty page")
+    pub(crate) fn add_dict_string(&mut self, value: &str) -> Result<(), Error> {
+        Self::ensure_encodable_length(value.len())?;
+        let bytes_needed = size_of::<u16>() + self.dictionary.bytes_needed_for(value);
+        if bytes_needed > self.free_space() {
+            return Err(Error::PageFull);
+        }
+        let id = self.dictionary.id_for(value)?;
+        self.add_field(
+            |destination, current_write_offset| {
+                U16EncoderDecoder.encode(&id, destination, current_write_offset)
+            },
+            U16EncoderDecoder.bytes_needed_for_encoding(&id),
+            FieldType::TypeDictStr,
+        )
+    }
+
+    pub(crate) fn add_char(&mut self, value: char) -> Result<(), Error> {
+        self.add_field(
+            |destination, current_write_offset| {
+                CharEncoderDecoder.encode(value, destination, current_write_offset)
+            },
+            CharEncoderDecoder::SIZE,
+            FieldType::TypeChar,
+        )
+    }
+
+    pub(crate) fn get_char(&self, index: usize) -> Result<Option<char>, Error> {
+        if self.is_null(index) {
+            return Ok(None);
+        }
+        self.ensure_field_type(index, FieldType::TypeChar)?;
+        self.get(
+            |starting_offset| CharEncoderDecoder.decode(&self.buffer, starting_offset),
+            index,
+        )
+        .map(|decoded| decoded.map(|(value, _)| value))
+        .transpose()
+    }
+
+    pub(crate) fn get_string(&self, index: usize) -> Result<Option<&str>, Error> {
+        self.get_str(index)
+    }
+
+    /// Borrows the string directly from the page without copying it.
+    /// Returns an error if the stored bytes are not valid UTF-8.
+    pub(crate) fn get_str(&self, index: usize) -> Result<Option<&str>, Error> {
+        match self.string_bytes(index)? {
+            Some(bytes) => std::str::from_utf8(bytes)
+                .map(Some)
+                .map_err(|_| Error::Decode(format!("field at index {} is not valid UTF-8", index))),
+            None => Ok(None),
+        }
+    }
+
+    /// Replaces invalid UTF-8 sequences with U+FFFD, copying the string only when it does so.
+    pub(crate) fn get_string_lossy(&self, index: usize) -> Result<Option<Cow<'_, str>>, Error> {
+        Ok(self.string_bytes(index)?.map(String::from_utf8_lossy))
+    }
+
+    pub(crate) fn field(&self, index: usize) -> Option<FieldValue> {
+        let starting_offset = self.starting_offsets.offset_value_at(index)? as usize;
+        decode_field(
+            &self.buffer,
+            self.types.type_at(index)?,
+            starting_offset,
+            &self.dictionary,
+        )
+    }
+
+    pub(crate) fn add_field_value(&mut self, value: FieldValue) -> Result<(), Error> {
+        match value {
+            FieldValue::U8(value) => self.add_u8(value),
+            FieldValue::U16(value) => self.add_u16(value),
+            FieldValue::U32(value) => self.add_u32(value),
+            FieldValue::Bytes(value) => self.add_bytes(value),
+            FieldValue::Str(value) => self.add_string(&value),
+            FieldValue::Overflow(pointer) => self.add_field(
+                |destination, current_write_offset| {
+                    pointer.encode(destination, current_write_offset)
+                },
+                pointer.bytes_needed_for_encoding(),
+                FieldType::TypeOverflow,
+            ),
+            FieldValue::Null => self.add_null(),
+            FieldValue::Timestamp(value) => self.add_timestamp(value),
+            FieldValue::Uuid(value) => self.add_uuid(value),
+            FieldValue::DictStr(value) => self.add_dict_string(&value),
+            FieldValue::Char(value) => self.add_char(value),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.starting_offsets.length()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn field_types(&self) -> Vec<FieldType> {
+        self.types.as_slice().to_vec()
+    }
+
+    /// Number of bytes occupied by the fields, i.e. the offset where the next field is written.
+    pub(crate) fn data_len(&self) -> usize {
+        self.current_write_offset
+    }
+
+    pub(crate) fn iter_fields(&self) -> impl Iterator<Item = FieldValue> + '_ {
+        (0..self.len()).filter_map(|index| self.field(index))
+    }
+
+    pub(crate) fn encoded_field(&self, index: usize) -> Option<&[u8]> {
+        let starting_offset = self.starting_offsets.offset_value_at(index)? as usize;
+        let end_offset = self
+            .types
+            .type_at(index)?
+            .end_offset_post_decode(&self.buffer, starting_offset);
+        Some(&self.buffer[starting_offset..end_offset])
+    }
+
+    pub(crate) fn overwrite_encoded_field(
+        &mut self,
+        encoded: &[u8],
+        index: usize,
+    ) -> Result<(), Error> {
+        self.mutate_field(
+            |destination, starting_offset| {
+                destination[starting_offset..starting_offset + encoded.len()]
+                    .copy_from_slice(encoded);
+                encoded.len()
+            },
+            encoded.len(),
+            index,
+        )
+    }
+
+    pub(crate) fn delete_field(&mut self, index: usize) -> bool {
+        if self.starting_offsets.remove_offset(index).is_none() {
+            return false;
         }
+        self.types.remove(index);
+        true
+    }
 
+    pub(crate) fn compact(&mut self) {
+        let live_fields: Vec<Vec<u8>> = (0..self.len())
+            .filter_map(|index| self.encoded_field(index).map(|encoded| encoded.to_vec()))
+            .collect();
+
+        self.starting_offsets = StartingOffsets::new();
+        self.current_write_offset = 0;
+        for encoded in live_fields {
+            let starting_offset = self.current_write_offset;
+            self.buffer[starting_offset..starting_offset + encoded.len()].copy_from_slice(&encoded);
+            self.starting_offsets
+                .add_offset(starting_offset)
+                .expect("compaction only moves offsets towards the start of the page");
+            self.current_write_offset += encoded.len();
+        }
+    }
+
+    pub(crate) fn encode(&mut self) -> &[u8] {
         let mut encoder = PageEncoder {
             buffer: &mut self.buffer,
             starting_offsets: &self.starting_offsets,
             types: &self.types,
+            dictionary: &self.dictionary,
         };
         encoder.encode();
         &self.buffer
     }
 
-    fn assert_field_type(&self, index: usize, expected: FieldType) {
-        assert_eq!(Some(&expected), self.types.type_at(index))
+    pub(crate) fn hex_dump(&self) -> String {
+        let end_of_offsets = self.buffer.len() - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS;
+        let end_of_types = end_of_offsets - self.starting_offsets.size_in_bytes();
+        let start_of_types = end_of_types - self.types.size_in_bytes();
+        let mut regions = vec![
+            Region {
+                range: start_of_types..end_of_types,
+                label: "types".to_string(),
+            },
+            Region {
+                range: end_of_types..end_of_offsets,
+                label: "starting offsets".to_string(),
+            },
+            Region {
+                range: end_of_offsets..self.buffer.len(),
+                label: format!("number of offsets = {}", self.len()),
+            },
+        ];
+        if !self.dictionary.is_empty() {
+            regions.push(Region {
+                range: start_of_types - self.dictionary.size_in_bytes()..start_of_types,
+                label: "dictionary".to_string(),
+            });
+        }
+        hex_dump(&self.buffer, &regions)
+    }
+
+    /// Size of a page holding `fields` whose variable-length payloads add up to `payload_len`,
+    /// including the footer. Dictionary entries of dictionary encoded strings are not counted.
+    pub(crate) fn estimate_size(fields: &[FieldType], payload_len: usize) -> usize {
+        let size_of_fields: usize = fields
+            .iter()
+            .map(|field_type| field_type.reserved_size_in_bytes())
+            .sum();
+        size_of_fields
+            + payload_len
+            + StartingOffsets::size_in_bytes_for(fields.len())
+            + Fields::size_in_bytes_for(fields.len())
+            + RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+    }
+
+    /// Adds a value of any type with an encoder. The encoding must have the layout of
+    /// `field_type`, which is what decoding the page validates the field against.
+    pub(crate) fn add_with<T: ?Sized + ToOwned, E: EncoderDecoder<T>>(
+        &mut self,
+        encoder: &E,
+        value: &T,
+        field_type: FieldType,
+    ) -> Result<(), Error> {
+        self.add_field(
+            |destination, current_write_offset| {
+                encoder.encode(value, destination, current_write_offset)
+            },
+            encoder.bytes_needed_for_encoding(value),
+            field_type,
+        )
     }
 
+    pub(crate) fn get_with<T: ?Sized + ToOwned, E: EncoderDecoder<T>>(
+        &self,
+        encoder: &E,
+        index: usize,
+        field_type: FieldType,
+    ) -> Result<Option<T::Owned>, Error> {
+        if self.is_null(index) {
+            return Ok(None);
+        }
+        self.ensure_field_type(index, field_type)?;
+        Ok(self.get(
+            |starting_offset| encoder.decode(&self.buffer, starting_offset).0.into_owned(),
+            index,
+        ))
+    }
+
+    pub(crate) fn free_space(&self) -> usize {
+        self.free_space_for_fields(1)
+    }
+
+    pub(crate) fn free_space_for_fields(&self, number_of_new_fields: usize) -> usize {
+        self.buffer.len().saturating_sub(
+            self.current_write_offset + self.reserved_size_for(number_of_new_fields),
+        )
+    }
+
+    fn reserved_size_for(&self, number_of_new_fields: usize) -> usize {
+        let number_of_fields = self.len() + number_of_new_fields;
+        RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            + self.dictionary.size_in_bytes()
+            + StartingOffsets::size_in_bytes_for(number_of_fields)
+            + Fields::size_in_bytes_for(number_of_fields)
+    }
+
+    fn string_bytes(&self, index: usize) -> Result<Option<&[u8]>, Error> {
+        if self.is_null(index) {
+            return Ok(None);
+        }
+        if self.types.type_at(index) == Some(&FieldType::TypeDictStr) {
+            return Ok(self.dictionary_entry(index).map(str::as_bytes));
+        }
+        self.ensure_field_type(index, FieldType::TypeString)?;
+        let bytes = self.get(
+            |starting_offset| BytesEncoderDecoder.decode(&self.buffer, starting_offset).0,
+            index,
+        );
+        Ok(bytes.map(assert_borrowed_type))
+    }
+
+    fn dictionary_entry(&self, index: usize) -> Option<&str> {
+        let id = self.get(
+            |starting_offset| *U16EncoderDecoder.decode(&self.buffer, starting_offset).0,
+            index,
+        )?;
+        self.dictionary.get(id)
+    }
+
+    fn ensure_encodable_length(length: usize) -> Result<(), Error> {
+        if length > BytesEncoderDecoder::MAX_LENGTH {
+            return Err(Error::ValueTooLarge {
+                length,
+                maximum: BytesEncoderDecoder::MAX_LENGTH,
+            });
+        }
+        Ok(())
+    }
+
+    fn ensure_field_type(&self, index: usize, expected: FieldType) -> Result<(), Error> {
+        match self.types.type_at(index) {
+            Some(field_type) if *field_type != expected => Err(Error::TypeMismatch { index }),
+            _ => Ok(()),
+        }
+    }
+
+    // Compares against the footer directly rather than free_space(), which saturates at zero
+    // and would let a zero-width null through on a page whose footer is already full.
     fn add_field<F: Fn(&mut [u8], usize) -> BytesNeededForEncoding>(
         &mut self,
         encode_fn: F,
+        bytes_needed_for_encoding: BytesNeededForEncoding,
         field_type: FieldType,
-    ) {
-        let bytes_needed_for_encoding = encode_fn(&mut self.buffer, self.current_write_offset);
+    ) -> Result<(), Error> {
+        if self.current_write_offset + bytes_needed_for_encoding + self.reserved_size_for(1)
+            > self.buffer.len()
+        {
+            return Err(Error::PageFull);
+        }
         self.starting_offsets
-            .add_offset(self.current_write_offset as u32);
+            .add_offset(self.current_write_offset)?;
+        encode_fn(&mut self.buffer, self.current_write_offset);
         self.types.add(field_type);
         self.current_write_offset += bytes_needed_for_encoding;
+        Ok(())
     }
 
+    fn insert_field<F: Fn(&mut [u8], usize) -> BytesNeededForEncoding>(
+        &mut self,
+        encode_fn: F,
+        bytes_needed_for_encoding: BytesNeededForEncoding,
+        field_type: FieldType,
+        index: usize,
+    ) -> Result<(), Error> {
+        if index > self.len() {
+            return Err(Error::FieldNotFound { index });
+        }
+        if bytes_needed_for_encoding > self.free_space() {
+            return Err(Error::PageFull);
+        }
+        let starting_offset = self
+            .starting_offsets
+            .offset_value_at(index)
+            .map_or(self.current_write_offset, |offset| offset as usize);
+
+        // Shifting and inserting the offsets validates them, so a rejected offset leaves the
+        // fields unmoved. The inserted offset is not above the shifted ones, so it cannot fail
+        // once the shift succeeded.
+        self.starting_offsets
+            .shift_from(index, bytes_needed_for_encoding as i64)?;
+        self.starting_offsets
+            .insert_offset(index, starting_offset)?;
+        self.buffer.copy_within(
+            starting_offset..self.current_write_offset,
+            starting_offset + bytes_needed_for_encoding,
+        );
+        encode_fn(&mut self.buffer, starting_offset);
+        self.types.insert(index, field_type);
+        self.current_write_offset += bytes_needed_for_encoding;
+        Ok(())
+    }
+
+    // A value of a different size moves the fields after it, so it is checked against the free
+    // space before any bytes are moved.
     fn mutate_field<F: Fn(&mut [u8], usize) -> BytesNeededForEncoding>(
         &mut self,
         encode_fn: F,
+        bytes_needed_for_encoding: BytesNeededForEncoding,
         index: usize,
-    ) {
-        encode_fn(
-            &mut self.buffer,
-            *(self.starting_offsets.offset_at(index).unwrap()) as usize,
+    ) -> Result<(), Error> {
+        let current_length = self
+            .encoded_field(index)
+            .ok_or(Error::FieldNotFound { index })?
+            .len();
+        if bytes_needed_for_encoding > current_length + self.free_space_for_fields(0) {
+            return Err(Error::PageFull);
+        }
+        let starting_offset = self.starting_offsets.offset_value_at(index).unwrap() as usize;
+        let delta = bytes_needed_for_encoding as i64 - current_length as i64;
+        self.starting_offsets.shift_from(index + 1, delta)?;
+
+        self.buffer.copy_within(
+            starting_offset + current_length..self.current_write_offset,
+            starting_offset + bytes_needed_for_encoding,
         );
+        encode_fn(&mut self.buffer, starting_offset);
+        self.current_write_offset = (self.current_write_offset as i64 + delta) as usize;
+        Ok(())
     }
 
     fn get<T, F: Fn(usize) -> T>(&self, decode_fn: F, index: usize) -> Option<T> {
         self.starting_offsets
-            .offset_at(index)
-            .map(|starting_offset| decode_fn(*starting_offset as usize))
+            .offset_value_at(index)
+            .map(|starting_offset| decode_fn(starting_offset as usize))
     }
 }
 
+pub(crate) fn decode_field(
+    buffer: &[u8],
+    field_type: &FieldType,
+    starting_offset: usize,
+    dictionary: &Dictionary,
+) -> Option<FieldValue> {
+    let value = match field_type {
+        FieldType::TypeU8 => FieldValue::U8(*U8EncoderDecoder.decode(buffer, starting_offset).0),
+        FieldType::TypeU16 => FieldValue::U16(*U16EncoderDecoder.decode(buffer, starting_offset).0),
+        FieldType::TypeU32 => FieldValue::U32(*U32EncoderDecoder.decode(buffer, starting_offset).0),
+        FieldType::TypeBytes => FieldValue::Bytes(
+            BytesEncoderDecoder
+                .decode(buffer, starting_offset)
+                .0
+                .into_owned(),
+        ),
+        FieldType::TypeString => FieldValue::Str(
+            StrEncoderDecoder
+                .decode(buffer, starting_offset)
+                .0
+                .into_owned(),
+        ),
+        FieldType::TypeOverflow => {
+            FieldValue::Overflow(OverflowPointer::decode(buffer, starting_offset).0)
+        }
+        FieldType::TypeNull => FieldValue::Null,
+        FieldType::TypeTimestamp => {
+            FieldValue::Timestamp(*TimestampEncoderDecoder.decode(buffer, starting_offset).0)
+        }
+        FieldType::TypeUuid => {
+            FieldValue::Uuid(*Fixed16EncoderDecoder.decode(buffer, starting_offset).0)
+        }
+        FieldType::TypeDictStr => FieldValue::DictStr(
+            dictionary
+                .get(*U16EncoderDecoder.decode(buffer, starting_offset).0)?
+                .to_string(),
+        ),
+        FieldType::TypeChar => {
+            FieldValue::Char(CharEncoderDecoder.decode(buffer, starting_offset).ok()?.0)
+        }
+    };
+    Some(value)
+}
+
 macro_rules! generate_get_fixed_size {
     ($method_name:ident, $type:ty, $field_type:expr, $encoder_name:ident) => {
         impl BufferPage {
-            pub(crate) fn $method_name(&self, index: usize) -> Option<$type> {
-                self.assert_field_type(index, $field_type);
+            pub(crate) fn $method_name(&self, index: usize) -> Result<Option<$type>, Error> {
+                if self.is_null(index) {
+                    return Ok(None);
+                }
+                self.ensure_field_type(index, $field_type)?;
                 let encoder = $encoder_name;
 
-                self.get(
+                Ok(self.get(
                     |starting_offset| encoder.decode(&self.buffer, starting_offset).0.into_owned(),
                     index,
-                )
+                ))
             }
         }
     };
@@ -160,12 +658,13 @@ macro_rules! generate_get_fixed_size {
 macro_rules! generate_add_fixed_size {
     ($method_name:ident, $type:ty, $field_type:expr, $encoder_name:ident) => {
         impl BufferPage {
-            pub(crate) fn $method_name(&mut self, value: $type) {
+            pub(crate) fn $method_name(&mut self, value: $type) -> Result<(), Error> {
                 let encoder = $encoder_name;
                 self.add_field(
                     |destination, current_write_offset| {
                         encoder.encode(&value, destination, current_write_offset)
                     },
+                    encoder.bytes_needed_for_encoding(&value),
                     $field_type,
                 )
             }
@@ -176,15 +675,34 @@ macro_rules! generate_add_fixed_size {
 macro_rules! generate_mutate_fixed_size {
     ($method_name:ident, $type:ty, $field_type:expr, $encoder_name:ident) => {
         impl BufferPage {
-            pub(crate) fn $method_name(&mut self, value: $type, index: usize) {
-                self.assert_field_type(index, $field_type);
+            pub(crate) fn $method_name(&mut self, value: $type, index: usize) -> Result<(), Error> {
+                self.ensure_field_type(index, $field_type)?;
                 let encoder = $encoder_name;
                 self.mutate_field(
                     |destination, current_write_offset| {
                         encoder.encode(&value, destination, current_write_offset)
                     },
+                    encoder.bytes_needed_for_encoding(&value),
                     index,
-                );
+                )
+            }
+        }
+    };
+}
+
+macro_rules! generate_insert_fixed_size {
+    ($method_name:ident, $type:ty, $field_type:expr, $encoder_name:ident) => {
+        impl BufferPage {
+            pub(crate) fn $method_name(&mut self, value: $type, index: usize) -> Result<(), Error> {
+                let encoder = $encoder_name;
+                self.insert_field(
+                    |destination, starting_offset| {
+                        encoder.encode(&value, destination, starting_offset)
+                    },
+                    encoder.bytes_needed_for_encoding(&value),
+                    $field_type,
+                    index,
+                )
             }
         }
     };
@@ -193,191 +711,1075 @@ macro_rules! generate_mutate_fixed_size {
 generate_get_fixed_size!(get_u8, u8, FieldType::TypeU8, U8EncoderDecoder);
 generate_add_fixed_size!(add_u8, u8, FieldType::TypeU8, U8EncoderDecoder);
 generate_mutate_fixed_size!(mutate_u8, u8, FieldType::TypeU8, U8EncoderDecoder);
+generate_insert_fixed_size!(insert_u8_at, u8, FieldType::TypeU8, U8EncoderDecoder);
 generate_get_fixed_size!(get_u16, u16, FieldType::TypeU16, U16EncoderDecoder);
 generate_add_fixed_size!(add_u16, u16, FieldType::TypeU16, U16EncoderDecoder);
 generate_mutate_fixed_size!(mutate_u16, u16, FieldType::TypeU16, U16EncoderDecoder);
+generate_insert_fixed_size!(insert_u16_at, u16, FieldType::TypeU16, U16EncoderDecoder);
 generate_get_fixed_size!(get_u32, u32, FieldType::TypeU32, U32EncoderDecoder);
 generate_add_fixed_size!(add_u32, u32, FieldType::TypeU32, U32EncoderDecoder);
 generate_mutate_fixed_size!(mutate_u32, u32, FieldType::TypeU32, U32EncoderDecoder);
+generate_insert_fixed_size!(insert_u32_at, u32, FieldType::TypeU32, U32EncoderDecoder);
+generate_get_fixed_size!(
+    get_timestamp,
+    SystemTime,
+    FieldType::TypeTimestamp,
+    TimestampEncoderDecoder
+);
+generate_add_fixed_size!(
+    add_timestamp,
+    SystemTime,
+    FieldType::TypeTimestamp,
+    TimestampEncoderDecoder
+);
+generate_mutate_fixed_size!(
+    mutate_timestamp,
+    SystemTime,
+    FieldType::TypeTimestamp,
+    TimestampEncoderDecoder
+);
+generate_insert_fixed_size!(
+    insert_timestamp_at,
+    SystemTime,
+    FieldType::TypeTimestamp,
+    TimestampEncoderDecoder
+);
+generate_get_fixed_size!(
+    get_uuid,
+    [u8; 16],
+    FieldType::TypeUuid,
+    Fixed16EncoderDecoder
+);
+generate_add_fixed_size!(
+    add_uuid,
+    [u8; 16],
+    FieldType::TypeUuid,
+    Fixed16EncoderDecoder
+);
+generate_mutate_fixed_size!(
+    mutate_uuid,
+    [u8; 16],
+    FieldType::TypeUuid,
+    Fixed16EncoderDecoder
+);
+generate_insert_fixed_size!(
+    insert_uuid_at,
+    [u8; 16],
+    FieldType::TypeUuid,
+    Fixed16EncoderDecoder
+);
 
 #[cfg(test)]
 mod tests {
+    use crate::buffer::field_types::{FieldType, FieldValue};
     use crate::buffer::page::BufferPage;
+    use crate::buffer::page_encoder_decoder::PageDecoder;
+    use crate::encodex::fixed16_encoder_decoder::Fixed16EncoderDecoder;
+    use crate::encodex::{BytesNeededForEncoding, EncoderDecoder, EndOffset};
+    use crate::error::Error;
+    use crate::file::file_manager::FileManager;
     use crate::page::Page;
+    use byteorder::ByteOrder;
+    use std::borrow::Cow;
+    use std::time::{Duration, UNIX_EPOCH};
+    use tempfile::NamedTempFile;
 
     const BLOCK_SIZE: usize = 4096;
 
     #[test]
-    #[should_panic]
     fn attempt_to_decode_with_an_empty_buffer() {
-        BufferPage::decode_from(vec![]);
+        assert!(matches!(
+            BufferPage::decode_from(vec![]),
+            Err(Error::Decode(_))
+        ));
     }
 
     #[test]
     fn add_a_single_field_and_get_the_value() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u8(250);
+        page.add_u8(250).unwrap();
 
-        assert_eq!(Some(250), page.get_u8(0));
+        assert_eq!(Some(250), page.get_u8(0).unwrap());
     }
 
     #[test]
     fn add_a_couple_of_fields_and_get_the_values() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u8(250);
-        page.add_u16(500);
+        page.add_u8(250).unwrap();
+        page.add_u16(500).unwrap();
 
-        assert_eq!(Some(250), page.get_u8(0));
-        assert_eq!(Some(500), page.get_u16(1));
+        assert_eq!(Some(250), page.get_u8(0).unwrap());
+        assert_eq!(Some(500), page.get_u16(1).unwrap());
     }
 
     #[test]
     fn add_a_few_fields_and_get_the_values() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u8(250);
-        page.add_string("PebbleDB is an LSM-based storage engine");
-        page.add_bytes(b"RocksDB is an LSM-based storage engine".to_vec());
-        page.add_u32(310);
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB is an LSM-based storage engine")
+            .unwrap();
+        page.add_bytes(b"RocksDB is an LSM-based storage engine".to_vec())
+            .unwrap();
+        page.add_u32(310).unwrap();
 
-        assert_eq!(Some(250), page.get_u8(0));
+        assert_eq!(Some(250), page.get_u8(0).unwrap());
         assert_eq!(
             Some("PebbleDB is an LSM-based storage engine"),
-            page.get_string(1)
+            page.get_string(1).unwrap()
         );
         assert_eq!(
             Some("RocksDB is an LSM-based storage engine".as_bytes()),
-            page.get_bytes(2)
+            page.get_bytes(2).unwrap()
         );
-        assert_eq!(Some(310), page.get_u32(3));
+        assert_eq!(Some(310), page.get_u32(3).unwrap());
     }
 
     #[test]
-    #[should_panic]
-    fn attempt_to_decode_an_empty_page() {
+    fn encode_and_decode_an_empty_page() {
         let mut page = BufferPage::new(BLOCK_SIZE);
 
-        page.encode();
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert!(decoded.is_empty());
+        assert_eq!(page.free_space(), decoded.free_space());
+    }
+
+    #[test]
+    fn encode_a_page_after_deleting_all_of_its_fields() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(250).unwrap();
+        assert!(page.delete_field(0));
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert!(decoded.is_empty());
     }
 
     #[test]
     fn decode_a_page_with_single_field() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u8(250);
+        page.add_u8(250).unwrap();
 
         let encoded = page.encode();
-        let decoded = BufferPage::decode_from(encoded.to_vec());
+        let decoded = BufferPage::decode_from(encoded.to_vec()).unwrap();
 
-        assert_eq!(Some(250), decoded.get_u8(0));
+        assert_eq!(Some(250), decoded.get_u8(0).unwrap());
     }
 
     #[test]
     fn decode_a_page_with_few_fields() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u8(250);
-        page.add_string("PebbleDB is an LSM-based storage engine");
-        page.add_bytes(b"RocksDB is an LSM-based storage engine".to_vec());
-        page.add_u16(500);
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB is an LSM-based storage engine")
+            .unwrap();
+        page.add_bytes(b"RocksDB is an LSM-based storage engine".to_vec())
+            .unwrap();
+        page.add_u16(500).unwrap();
 
         let encoded = page.encode();
-        let decoded = BufferPage::decode_from(encoded.to_vec());
+        let decoded = BufferPage::decode_from(encoded.to_vec()).unwrap();
 
-        assert_eq!(Some(250), decoded.get_u8(0));
+        assert_eq!(Some(250), decoded.get_u8(0).unwrap());
         assert_eq!(
             Some("PebbleDB is an LSM-based storage engine"),
-            decoded.get_string(1)
+            decoded.get_string(1).unwrap()
         );
         assert_eq!(
             Some("RocksDB is an LSM-based storage engine".as_bytes()),
-            decoded.get_bytes(2)
+            decoded.get_bytes(2).unwrap()
         );
-        assert_eq!(Some(500), decoded.get_u16(3));
+        assert_eq!(Some(500), decoded.get_u16(3).unwrap());
     }
 
     #[test]
     fn mutate_an_u8() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u8(50);
-        page.mutate_u8(252, 0);
+        page.add_u8(50).unwrap();
+        page.mutate_u8(252, 0).unwrap();
 
-        assert_eq!(Some(252), page.get_u8(0));
+        assert_eq!(Some(252), page.get_u8(0).unwrap());
     }
 
     #[test]
     fn mutate_an_u16() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u16(50);
-        page.mutate_u16(252, 0);
+        page.add_u16(50).unwrap();
+        page.mutate_u16(252, 0).unwrap();
 
-        assert_eq!(Some(252), page.get_u16(0));
+        assert_eq!(Some(252), page.get_u16(0).unwrap());
     }
 
     #[test]
     fn mutate_an_u32() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_u32(50);
-        page.mutate_u32(252, 0);
+        page.add_u32(50).unwrap();
+        page.mutate_u32(252, 0).unwrap();
 
-        assert_eq!(Some(252), page.get_u32(0));
+        assert_eq!(Some(252), page.get_u32(0).unwrap());
     }
 
     #[test]
     fn mutate_bytes() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_bytes(b"Bolt-DB".to_vec());
-        page.mutate_bytes(b"RocksDB".to_vec(), 0);
+        page.add_bytes(b"Bolt-DB".to_vec()).unwrap();
+        page.mutate_bytes(b"RocksDB".to_vec(), 0).unwrap();
 
-        assert_eq!(Some("RocksDB".as_bytes()), page.get_bytes(0));
+        assert_eq!(Some("RocksDB".as_bytes()), page.get_bytes(0).unwrap());
     }
 
     #[test]
     fn mutate_string() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_string("Bolt-DB");
-        page.mutate_string("RocksDB", 0);
+        page.add_string("Bolt-DB").unwrap();
+        page.mutate_string("RocksDB", 0).unwrap();
 
-        assert_eq!(Some("RocksDB"), page.get_string(0));
+        assert_eq!(Some("RocksDB"), page.get_string(0).unwrap());
+    }
+
+    #[test]
+    fn mutate_a_string_to_values_of_a_different_length() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("Bolt-DB").unwrap();
+        page.add_u16(250).unwrap();
+
+        page.mutate_string("RocksDB is an LSM based storage engine", 0)
+            .unwrap();
+        assert_eq!(
+            Some("RocksDB is an LSM based storage engine"),
+            page.get_string(0).unwrap()
+        );
+        assert_eq!(Some(250), page.get_u16(1).unwrap());
+
+        page.mutate_string("LMDB", 0).unwrap();
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some("LMDB"), decoded.get_string(0).unwrap());
+        assert_eq!(Some(250), decoded.get_u16(1).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_mutate_a_string_to_a_value_larger_than_the_free_space() {
+        let mut page = BufferPage::new(64);
+        page.add_string("Bolt-DB").unwrap();
+        page.add_u16(250).unwrap();
+
+        assert!(matches!(
+            page.mutate_string(&"RocksDB".repeat(10), 0),
+            Err(Error::PageFull)
+        ));
+        assert_eq!(Some("Bolt-DB"), page.get_string(0).unwrap());
+        assert_eq!(Some(250), page.get_u16(1).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_mutate_a_non_existent_field() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(250).unwrap();
+
+        assert!(matches!(
+            page.mutate_u8(252, 1),
+            Err(Error::FieldNotFound { index: 1 })
+        ));
+        assert!(matches!(
+            page.mutate_string("RocksDB", 3),
+            Err(Error::FieldNotFound { index: 3 })
+        ));
+        assert_eq!(Some(250), page.get_u8(0).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_mutate_a_field_with_a_different_type() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(250).unwrap();
+
+        assert!(matches!(
+            page.mutate_u16(252, 0),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn insert_a_field_between_two_existing_fields() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("BoltDB").unwrap();
+        page.add_u16(500).unwrap();
+        page.insert_u16_at(250, 1).unwrap();
+        page.insert_string_at("RocksDB", 0).unwrap();
+        page.insert_u8_at(10, 4).unwrap();
+        page.insert_u32_at(70_000, 2).unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(
+            vec![
+                FieldValue::Str("RocksDB".to_string()),
+                FieldValue::Str("BoltDB".to_string()),
+                FieldValue::U32(70_000),
+                FieldValue::U16(250),
+                FieldValue::U16(500),
+                FieldValue::U8(10),
+            ],
+            decoded.iter_fields().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn attempt_to_insert_a_field_beyond_the_last_field() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(10).unwrap();
+
+        assert!(matches!(
+            page.insert_u16_at(250, 2),
+            Err(Error::FieldNotFound { index: 2 })
+        ));
+        assert_eq!(1, page.len());
+    }
+
+    #[test]
+    fn attempt_to_insert_a_field_in_a_full_page() {
+        let mut page = BufferPage::new(64);
+        page.add_bytes(vec![1; 40]).unwrap();
+        page.add_u8(10).unwrap();
+
+        assert!(matches!(
+            page.insert_bytes_at(vec![2; 20], 1),
+            Err(Error::PageFull)
+        ));
+        assert_eq!(Some(&[1; 40][..]), page.get_bytes(0).unwrap());
+        assert_eq!(Some(10), page.get_u8(1).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_add_fixed_size_fields_to_a_full_page() {
+        let mut page = BufferPage::new(32);
+        let added = (0..20)
+            .take_while(|value| page.add_u32(*value).is_ok())
+            .count();
+
+        assert_eq!(3, added);
+        assert!(matches!(page.add_u32(3), Err(Error::PageFull)));
+        assert!(matches!(page.add_char('🦀'), Err(Error::PageFull)));
+        assert!(matches!(page.add_null(), Err(Error::PageFull)));
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(
+            vec![FieldValue::U32(0), FieldValue::U32(1), FieldValue::U32(2)],
+            decoded.iter_fields().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn attempt_to_add_bytes_and_strings_larger_than_the_page() {
+        let mut page = BufferPage::new(64);
+
+        assert!(matches!(page.add_bytes(vec![1; 100]), Err(Error::PageFull)));
+        assert!(matches!(
+            page.add_string(&"r".repeat(100)),
+            Err(Error::PageFull)
+        ));
+        assert!(page.is_empty());
+        assert_eq!(0, page.data_len());
+    }
+
+    #[test]
+    fn attempt_to_add_bytes_longer_than_the_encodable_length() {
+        let mut page = BufferPage::new(128 * 1024);
+
+        assert!(matches!(
+            page.add_bytes(vec![1; 70 * 1024]),
+            Err(Error::ValueTooLarge {
+                length: 71680,
+                maximum: 65535
+            })
+        ));
+        assert!(matches!(
+            page.add_string(&"r".repeat(70 * 1024)),
+            Err(Error::ValueTooLarge { .. })
+        ));
+        assert!(page.is_empty());
+
+        page.add_bytes(vec![1; 10]).unwrap();
+        assert!(matches!(
+            page.mutate_bytes(vec![1; 70 * 1024], 0),
+            Err(Error::ValueTooLarge { .. })
+        ));
+        assert_eq!(Some(&[1; 10][..]), page.get_bytes(0).unwrap());
+    }
+
+    #[test]
+    fn add_a_null_field_between_fields_and_read_them_back() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(250).unwrap();
+        page.add_null().unwrap();
+        page.add_string("RocksDB").unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(250), decoded.get_u16(0).unwrap());
+        assert!(decoded.is_null(1));
+        assert_eq!(None, decoded.get_u16(1).unwrap());
+        assert_eq!(None, decoded.get_string(1).unwrap());
+        assert_eq!(Some("RocksDB"), decoded.get_string(2).unwrap());
+        assert_eq!(Some(FieldValue::Null), decoded.field(1));
+    }
+
+    #[test]
+    fn attempt_to_mutate_a_null_field() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_null().unwrap();
+        page.add_u16(250).unwrap();
+
+        assert!(matches!(
+            page.mutate_u16(320, 0),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+        assert_eq!(Some(250), page.get_u16(1).unwrap());
+    }
+
+    #[test]
+    fn add_timestamps_and_read_them_back_from_the_decoded_page() {
+        let instant = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let before_epoch = UNIX_EPOCH - Duration::from_millis(1_000_000_007);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_timestamp(instant).unwrap();
+        page.add_string("RocksDB").unwrap();
+        page.add_timestamp(before_epoch).unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(instant), decoded.get_timestamp(0).unwrap());
+        assert_eq!(Some("RocksDB"), decoded.get_string(1).unwrap());
+        assert_eq!(Some(before_epoch), decoded.get_timestamp(2).unwrap());
+        assert!(matches!(
+            decoded.get_timestamp(1),
+            Err(Error::TypeMismatch { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn mutate_and_insert_timestamps() {
+        let instant = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let later = instant + Duration::from_millis(42);
+        let earlier = instant - Duration::from_millis(42);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_timestamp(instant).unwrap();
+        page.add_string("RocksDB").unwrap();
+        page.mutate_timestamp(later, 0).unwrap();
+        page.insert_timestamp_at(earlier, 1).unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(later), decoded.get_timestamp(0).unwrap());
+        assert_eq!(Some(earlier), decoded.get_timestamp(1).unwrap());
+        assert_eq!(Some("RocksDB"), decoded.get_string(2).unwrap());
+        assert!(matches!(
+            page.mutate_timestamp(later, 2),
+            Err(Error::TypeMismatch { index: 2 })
+        ));
+    }
+
+    #[test]
+    fn add_a_uuid_among_other_fields_and_read_it_back_from_the_decoded_page() {
+        let uuid = [
+            0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44,
+            0x00, 0x00,
+        ];
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(250).unwrap();
+        page.add_uuid(uuid).unwrap();
+        page.add_string("RocksDB").unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(250), decoded.get_u16(0).unwrap());
+        assert_eq!(Some(uuid), decoded.get_uuid(1).unwrap());
+        assert_eq!(Some("RocksDB"), decoded.get_string(2).unwrap());
+        assert_eq!(2 + 16 + 9, decoded.current_write_offset);
+    }
+
+    #[test]
+    fn mutate_and_insert_uuids() {
+        let uuid = [0x11; 16];
+        let other = [0x22; 16];
+        let inserted = [0x33; 16];
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_uuid(uuid).unwrap();
+        page.add_u16(250).unwrap();
+        page.mutate_uuid(other, 0).unwrap();
+        page.insert_uuid_at(inserted, 1).unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(other), decoded.get_uuid(0).unwrap());
+        assert_eq!(Some(inserted), decoded.get_uuid(1).unwrap());
+        assert_eq!(Some(250), decoded.get_u16(2).unwrap());
+        assert!(matches!(
+            page.mutate_uuid(other, 2),
+            Err(Error::TypeMismatch { index: 2 })
+        ));
+    }
+
+    #[test]
+    fn data_len_of_a_decoded_page_matches_the_encoded_fields() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(10).unwrap();
+        page.add_string("RocksDB").unwrap();
+        page.add_u32(250).unwrap();
+        page.add_dict_string("LSM").unwrap();
+        page.add_null().unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        let encoded_size: usize = (0..decoded.len())
+            .map(|index| decoded.encoded_field(index).unwrap().len())
+            .sum();
+
+        assert_eq!(1 + 9 + 4 + 2, encoded_size);
+        assert_eq!(encoded_size, decoded.data_len());
+        assert_eq!(page.data_len(), decoded.data_len());
+    }
+
+    #[test]
+    fn add_an_emoji_char_and_read_it_back_from_the_decoded_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_char('🦀').unwrap();
+        page.add_char('y').unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some('🦀'), decoded.get_char(0).unwrap());
+        assert_eq!(Some('y'), decoded.get_char(1).unwrap());
+        assert_eq!(Some(FieldValue::Char('🦀')), decoded.field(0));
+        assert!(matches!(
+            decoded.get_u32(0),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn attempt_to_read_a_char_holding_an_invalid_scalar() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_char('y').unwrap();
+        byteorder::LittleEndian::write_u32(&mut page.buffer[0..4], 0xD800);
+
+        assert!(matches!(page.get_char(0), Err(Error::Decode(_))));
+        assert!(matches!(
+            PageDecoder::try_decode_page(page.encode().to_vec()),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn add_dictionary_encoded_strings_and_read_them_back_from_the_decoded_page() {
+        let mut dictionary_page = BufferPage::new(BLOCK_SIZE);
+        let mut string_page = BufferPage::new(BLOCK_SIZE);
+        for _ in 0..100 {
+            dictionary_page.add_dict_string("Rocks").unwrap();
+            string_page.add_string("Rocks").unwrap();
+        }
+        dictionary_page.add_dict_string("Bolt").unwrap();
+
+        assert!(dictionary_page.current_write_offset * 3 < string_page.current_write_offset);
+        assert!(dictionary_page.free_space() > string_page.free_space());
+
+        let decoded = BufferPage::decode_from(dictionary_page.encode().to_vec()).unwrap();
+        assert_eq!(101, decoded.len());
+        for index in 0..100 {
+            assert_eq!(Some("Rocks"), decoded.get_string(index).unwrap());
+        }
+        assert_eq!(Some("Bolt"), decoded.get_string(100).unwrap());
+        assert_eq!(
+            Some(FieldValue::DictStr("Rocks".to_string())),
+            decoded.field(0)
+        );
+    }
+
+    #[test]
+    fn attempt_to_add_a_dictionary_encoded_string_in_a_full_page() {
+        let mut page = BufferPage::new(32);
+        page.add_dict_string("RocksDB").unwrap();
+        page.add_dict_string("RocksDB").unwrap();
+
+        assert!(matches!(
+            page.add_dict_string("PebbleDB is an LSM based storage engine"),
+            Err(Error::PageFull)
+        ));
+        assert_eq!(2, page.len());
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(
+            Some(FieldValue::DictStr("RocksDB".to_string())),
+            decoded.field(1)
+        );
+    }
+
+    #[test]
+    fn get_a_str_borrowed_from_the_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("RocksDB").unwrap();
+
+        let str = page.get_str(0).unwrap().unwrap();
+        assert_eq!("RocksDB", str);
+        assert!(page.buffer.as_ptr_range().contains(&str.as_ptr()));
+        assert!(matches!(
+            page.get_string_lossy(0).unwrap(),
+            Some(Cow::Borrowed("RocksDB"))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_get_a_str_with_invalid_utf8() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("ab").unwrap();
+        page.buffer[2..4].copy_from_slice(&[0xFF, 0xFE]);
+
+        assert!(matches!(page.get_str(0), Err(Error::Decode(_))));
+        assert!(matches!(page.get_string(0), Err(Error::Decode(_))));
+        assert_eq!(
+            Some("\u{FFFD}\u{FFFD}".to_string()),
+            page.get_string_lossy(0).unwrap().map(Cow::into_owned)
+        );
+    }
+
+    #[test]
+    fn mutate_a_cloned_page_without_affecting_the_original() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("BoltDB").unwrap();
+        page.add_u16(250).unwrap();
+
+        let mut cloned = page.clone();
+        cloned.mutate_string("Rocks", 0).unwrap();
+        cloned.mutate_u16(320, 1).unwrap();
+        cloned.add_u8(10).unwrap();
+
+        assert_eq!(Some("BoltDB"), page.get_string(0).unwrap());
+        assert_eq!(Some(250), page.get_u16(1).unwrap());
+        assert_eq!(2, page.len());
+
+        assert_eq!(Some("Rocks"), cloned.get_string(0).unwrap());
+        assert_eq!(Some(320), cloned.get_u16(1).unwrap());
+        assert_eq!(3, cloned.len());
+    }
+
+    #[test]
+    fn pages_with_identical_fields_are_equal() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("RocksDB").unwrap();
+        page.add_u16(250).unwrap();
+
+        let mut other_page = BufferPage::new(BLOCK_SIZE * 2);
+        other_page.buffer[BLOCK_SIZE..].fill(0xFF);
+        other_page.add_string("RocksDB").unwrap();
+        other_page.add_u16(250).unwrap();
+
+        assert!(page == other_page);
+    }
+
+    #[test]
+    fn pages_with_a_differing_field_are_not_equal() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("RocksDB").unwrap();
+        page.add_u16(250).unwrap();
+
+        let mut other_page = BufferPage::new(BLOCK_SIZE);
+        other_page.add_string("RocksDB").unwrap();
+        other_page.add_u32(250).unwrap();
+
+        assert!(page != other_page);
     }
 
     #[test]
     fn add_fields_and_then_mutate_those_fields_in_the_decoded_page() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_string("PebbleDB is an LSM-based key/value storage engine");
-        page.add_u8(80);
-        page.add_u16(160);
+        page.add_string("PebbleDB is an LSM-based key/value storage engine")
+            .unwrap();
+        page.add_u8(80).unwrap();
+        page.add_u16(160).unwrap();
 
         let encoded = page.encode();
-        let mut decoded = BufferPage::decode_from(encoded.to_vec());
+        let mut decoded = BufferPage::decode_from(encoded.to_vec()).unwrap();
 
-        decoded.mutate_string("Rocks-DB is an LSM-based key/value storage engine", 0);
-        decoded.mutate_u8(160, 1);
-        decoded.mutate_u16(320, 2);
+        decoded
+            .mutate_string("Rocks-DB is an LSM-based key/value storage engine", 0)
+            .unwrap();
+        decoded.mutate_u8(160, 1).unwrap();
+        decoded.mutate_u16(320, 2).unwrap();
 
         assert_eq!(
             Some("Rocks-DB is an LSM-based key/value storage engine"),
-            decoded.get_string(0)
+            decoded.get_string(0).unwrap()
         );
-        assert_eq!(Some(160), decoded.get_u8(1));
-        assert_eq!(Some(320), decoded.get_u16(2));
+        assert_eq!(Some(160), decoded.get_u8(1).unwrap());
+        assert_eq!(Some(320), decoded.get_u16(2).unwrap());
     }
 
     #[test]
     fn add_fields_in_the_decoded_page() {
         let mut page = BufferPage::new(BLOCK_SIZE);
-        page.add_string("PebbleDB is an LSM-based key/value storage engine");
-        page.add_u8(80);
-        page.add_u16(160);
+        page.add_string("PebbleDB is an LSM-based key/value storage engine")
+            .unwrap();
+        page.add_u8(80).unwrap();
+        page.add_u16(160).unwrap();
 
         let encoded = page.encode();
-        let mut decoded = BufferPage::decode_from(encoded.to_vec());
+        let mut decoded = BufferPage::decode_from(encoded.to_vec()).unwrap();
 
-        decoded.add_string("BoltDB");
+        decoded.add_string("BoltDB").unwrap();
 
         assert_eq!(
             Some("PebbleDB is an LSM-based key/value storage engine"),
-            decoded.get_string(0)
+            decoded.get_string(0).unwrap()
+        );
+        assert_eq!(Some(80), decoded.get_u8(1).unwrap());
+        assert_eq!(Some(160), decoded.get_u16(2).unwrap());
+        assert_eq!(Some("BoltDB"), decoded.get_string(3).unwrap());
+    }
+
+    #[test]
+    fn get_an_encoded_field() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(10).unwrap();
+        page.add_u16(500).unwrap();
+
+        assert_eq!(Some(&500u16.to_le_bytes()[..]), page.encoded_field(1));
+        assert_eq!(None, page.encoded_field(2));
+    }
+
+    #[test]
+    fn overwrite_an_encoded_field() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("Bolt-DB").unwrap();
+        page.add_u16(500).unwrap();
+
+        let mut other_page = BufferPage::new(BLOCK_SIZE);
+        other_page.add_string("RocksDB").unwrap();
+
+        page.overwrite_encoded_field(other_page.encoded_field(0).unwrap(), 0)
+            .unwrap();
+
+        assert_eq!(Some("RocksDB"), page.get_string(0).unwrap());
+        assert_eq!(Some(500), page.get_u16(1).unwrap());
+    }
+
+    #[test]
+    fn estimate_the_size_of_a_page_before_adding_fields() {
+        let fields = [
+            FieldType::TypeU8,
+            FieldType::TypeString,
+            FieldType::TypeBytes,
+            FieldType::TypeU32,
+            FieldType::TypeNull,
+            FieldType::TypeUuid,
+            FieldType::TypeChar,
+        ];
+        let estimated_size = BufferPage::estimate_size(&fields, "RocksDB".len() + "LSM".len());
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(10).unwrap();
+        page.add_string("RocksDB").unwrap();
+        page.add_bytes(b"LSM".to_vec()).unwrap();
+        page.add_u32(250).unwrap();
+        page.add_null().unwrap();
+        page.add_uuid([1; 16]).unwrap();
+        page.add_char('y').unwrap();
+
+        assert_eq!(1 + 9 + 5 + 4 + 16 + 4, page.data_len());
+        assert_eq!(BLOCK_SIZE - page.free_space_for_fields(0), estimated_size);
+        assert!(estimated_size > page.data_len());
+    }
+
+    struct F64EncoderDecoder;
+
+    impl EncoderDecoder<f64> for F64EncoderDecoder {
+        fn bytes_needed_for_encoding(&self, _source: &f64) -> BytesNeededForEncoding {
+            size_of::<f64>()
+        }
+
+        fn encode(
+            &self,
+            source: &f64,
+            destination: &mut [u8],
+            destination_starting_offset: usize,
+        ) -> BytesNeededForEncoding {
+            byteorder::LittleEndian::write_f64(
+                &mut destination[destination_starting_offset..],
+                *source,
+            );
+            size_of::<f64>()
+        }
+
+        fn decode<'a>(&self, encoded: &'a [u8], from_offset: usize) -> (Cow<'a, f64>, EndOffset) {
+            (
+                Cow::Owned(byteorder::LittleEndian::read_f64(&encoded[from_offset..])),
+                from_offset + size_of::<f64>(),
+            )
+        }
+    }
+
+    #[test]
+    fn add_an_f64_and_a_uuid_through_the_generic_path() {
+        let uuid: [u8; 16] = std::array::from_fn(|index| index as u8 + 1);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        // An f64 has the 8 byte layout of a timestamp.
+        page.add_with(&F64EncoderDecoder, &2.5, FieldType::TypeTimestamp)
+            .unwrap();
+        page.add_with(&Fixed16EncoderDecoder, &uuid, FieldType::TypeUuid)
+            .unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(
+            Some(2.5),
+            decoded
+                .get_with(&F64EncoderDecoder, 0, FieldType::TypeTimestamp)
+                .unwrap()
+        );
+        assert_eq!(Some(uuid), decoded.get_uuid(1).unwrap());
+        assert_eq!(
+            Some(uuid),
+            decoded
+                .get_with(&Fixed16EncoderDecoder, 1, FieldType::TypeUuid)
+                .unwrap()
         );
-        assert_eq!(Some(80), decoded.get_u8(1));
-        assert_eq!(Some(160), decoded.get_u16(2));
-        assert_eq!(Some("BoltDB"), decoded.get_string(3));
+        assert!(matches!(
+            decoded.get_with(&F64EncoderDecoder, 1, FieldType::TypeTimestamp),
+            Err(Error::TypeMismatch { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn attempt_to_add_a_value_through_the_generic_path_to_a_full_page() {
+        let mut page = BufferPage::new(64);
+        while page
+            .add_with(&F64EncoderDecoder, &2.5, FieldType::TypeTimestamp)
+            .is_ok()
+        {}
+
+        let number_of_fields = page.len();
+        assert!(matches!(
+            page.add_with(&F64EncoderDecoder, &2.5, FieldType::TypeTimestamp),
+            Err(Error::PageFull)
+        ));
+        assert_eq!(number_of_fields, page.len());
+        assert!(BufferPage::decode_from(page.encode().to_vec()).is_ok());
+    }
+
+    #[test]
+    fn free_space_of_a_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        let free_space = page.free_space();
+        page.add_u32(100).unwrap();
+
+        assert!(page.free_space() < free_space - 4);
+    }
+
+    #[test]
+    fn add_bytes_larger_than_a_block_and_read_them_back() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let overflow_file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let blob: Vec<u8> = (0..BLOCK_SIZE * 3).map(|byte| (byte % 251) as u8).collect();
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(500).unwrap();
+        page.add_bytes_or_overflow(blob.clone(), &file_manager, overflow_file_name)
+            .unwrap();
+        page.add_bytes_or_overflow(b"RocksDB".to_vec(), &file_manager, overflow_file_name)
+            .unwrap();
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(500), decoded.get_u16(0).unwrap());
+        assert_eq!(
+            blob,
+            decoded
+                .get_bytes_or_overflow(1, &file_manager)
+                .unwrap()
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            Some(b"RocksDB".as_slice()),
+            decoded
+                .get_bytes_or_overflow(2, &file_manager)
+                .unwrap()
+                .as_deref()
+        );
+    }
+
+    #[test]
+    fn attempt_to_add_overflowing_bytes_to_a_page_without_space_for_the_pointer() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let overflow_file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut page = BufferPage::new(64);
+        page.add_bytes(vec![1; 40]).unwrap();
+
+        assert!(matches!(
+            page.add_bytes_or_overflow(vec![2; 100], &file_manager, overflow_file_name),
+            Err(Error::PageFull)
+        ));
+        assert_eq!(1, page.len());
+        assert_eq!(
+            0,
+            file_manager.number_of_blocks(overflow_file_name).unwrap()
+        );
+    }
+
+    #[test]
+    fn delete_a_field() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(10).unwrap();
+        page.add_string("Bolt-DB").unwrap();
+
+        assert!(page.delete_field(0));
+        assert_eq!(Some("Bolt-DB"), page.get_string(0).unwrap());
+        assert!(!page.delete_field(1));
+    }
+
+    #[test]
+    fn compact_a_page_after_deleting_fields() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("PebbleDB is an LSM-based storage engine")
+            .unwrap();
+        page.add_u16(160).unwrap();
+        page.add_bytes(b"RocksDB is an LSM-based storage engine".to_vec())
+            .unwrap();
+        page.add_u32(320).unwrap();
+        page.add_string("Bolt-DB").unwrap();
+
+        assert!(page.delete_field(2));
+        assert!(page.delete_field(0));
+
+        let free_space = page.free_space();
+        page.compact();
+        assert!(page.free_space() > free_space);
+
+        assert_eq!(Some(160), page.get_u16(0).unwrap());
+        assert_eq!(Some(320), page.get_u32(1).unwrap());
+        assert_eq!(Some("Bolt-DB"), page.get_string(2).unwrap());
+
+        let decoded = BufferPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(160), decoded.get_u16(0).unwrap());
+        assert_eq!(Some(320), decoded.get_u32(1).unwrap());
+        assert_eq!(Some("Bolt-DB"), decoded.get_string(2).unwrap());
+        assert_eq!(page.free_space(), decoded.free_space());
+    }
+
+    #[test]
+    fn attempt_to_get_a_field_with_a_different_type() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("Bolt-DB").unwrap();
+
+        assert!(matches!(
+            page.get_u16(0),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+        assert!(matches!(
+            page.get_bytes(0),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+        assert_eq!(Some("Bolt-DB"), page.get_string(0).unwrap());
+    }
+
+    #[test]
+    fn get_a_field_that_does_not_exist() {
+        let page = BufferPage::new(BLOCK_SIZE);
+        assert_eq!(None, page.get_u16(0).unwrap());
+    }
+
+    #[test]
+    fn get_fields_without_knowing_their_types() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB").unwrap();
+        page.add_u16(500).unwrap();
+        page.add_bytes(b"RocksDB".to_vec()).unwrap();
+        page.add_u32(310).unwrap();
+
+        let fields: Vec<FieldValue> = (0..5).filter_map(|index| page.field(index)).collect();
+        assert_eq!(
+            vec![
+                FieldValue::U8(250),
+                FieldValue::Str("PebbleDB".to_string()),
+                FieldValue::U16(500),
+                FieldValue::Bytes(b"RocksDB".to_vec()),
+                FieldValue::U32(310),
+            ],
+            fields
+        );
+        assert_eq!(None, page.field(5));
+    }
+
+    #[test]
+    fn iterate_over_all_the_fields() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB").unwrap();
+        page.add_u16(500).unwrap();
+
+        let mut fields = page.iter_fields();
+        assert_eq!(Some(FieldValue::U8(250)), fields.next());
+        assert_eq!(Some(FieldValue::Str("PebbleDB".to_string())), fields.next());
+        assert_eq!(Some(FieldValue::U16(500)), fields.next());
+        assert_eq!(None, fields.next());
+    }
+
+    #[test]
+    fn number_of_fields_in_a_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        assert!(page.is_empty());
+
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB").unwrap();
+        page.add_u16(500).unwrap();
+
+        assert_eq!(3, page.len());
+        assert!(!page.is_empty());
+    }
+
+    #[test]
+    fn field_types_of_a_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        assert!(page.field_types().is_empty());
+
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB").unwrap();
+        page.add_u16(500).unwrap();
+
+        assert_eq!(
+            vec![FieldType::TypeU8, FieldType::TypeString, FieldType::TypeU16],
+            page.field_types()
+        );
+    }
+
+    #[test]
+    fn add_field_values() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        let values = vec![
+            FieldValue::U8(250),
+            FieldValue::Str("PebbleDB".to_string()),
+            FieldValue::Bytes(b"RocksDB".to_vec()),
+            FieldValue::U32(310),
+        ];
+        values
+            .iter()
+            .cloned()
+            .for_each(|value| page.add_field_value(value).unwrap());
+
+        assert_eq!(values, page.iter_fields().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn hex_dump_of_a_page() {
+        let mut page = BufferPage::new(64);
+        page.add_u8(250).unwrap();
+        page.add_string("Bolt").unwrap();
+        page.encode();
+
+        let dump = page.hex_dump();
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with("00000000  fa 04 00 42 6f 6c 74"));
+        assert!(lines[3].contains("types"));
+        assert!(lines[3].contains("starting offsets"));
+        assert!(lines[3].ends_with("number of offsets = 2"));
     }
 }
@@ -1,22 +1,33 @@
-use crate::buffer::field_types::Fields;
+use crate::buffer::dictionary::Dictionary;
+use crate::buffer::field_types::{FieldType, Fields};
 use crate::buffer::page::BufferPage;
+use crate::error::Error;
 use crate::file::starting_offsets::StartingOffsets;
 use byteorder::ByteOrder;
 
-const RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS: usize = size_of::<u16>();
+pub(crate) const RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS: usize = size_of::<u16>();
 
 pub(crate) struct PageEncoder<'a> {
     pub(crate) buffer: &'a mut [u8],
     pub(crate) starting_offsets: &'a StartingOffsets,
     pub(crate) types: &'a Fields,
+    pub(crate) dictionary: &'a Dictionary,
 }
 
 pub(crate) struct PageDecoder;
 
+pub(crate) struct PageLayout {
+    pub(crate) starting_offsets: StartingOffsets,
+    pub(crate) types: Fields,
+    pub(crate) dictionary: Dictionary,
+    pub(crate) current_write_offset: usize,
+}
+
 impl PageEncoder<'_> {
     pub(crate) fn encode(&mut self) {
         self.write_encoded_starting_offsets(&self.starting_offsets.encode());
         self.write_types(&self.types.encode());
+        self.write_dictionary(&self.dictionary.encode());
         self.write_number_of_starting_offsets();
     }
 
@@ -42,6 +53,22 @@ impl PageEncoder<'_> {
             .copy_from_slice(encoded_types);
     }
 
+    fn write_dictionary(&mut self, encoded_dictionary: &[u8]) {
+        if self.dictionary.is_empty() {
+            return;
+        }
+        let encoded_page = &mut self.buffer;
+        let offset_to_write_dictionary = encoded_page.len()
+            - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            - self.starting_offsets.size_in_bytes()
+            - self.types.size_in_bytes()
+            - encoded_dictionary.len();
+
+        encoded_page
+            [offset_to_write_dictionary..offset_to_write_dictionary + encoded_dictionary.len()]
+            .copy_from_slice(encoded_dictionary);
+    }
+
     fn write_number_of_starting_offsets(&mut self) {
         let encoded_page = &mut self.buffer;
         let encoded_page_length = encoded_page.len();
@@ -54,34 +81,177 @@ impl PageEncoder<'_> {
 }
 
 impl PageDecoder {
-    pub(crate) fn decode_page(buffer: Vec<u8>) -> BufferPage {
+    pub(crate) fn decode_page(buffer: Vec<u8>) -> Result<BufferPage, Error> {
+        if buffer.len() < RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS {
+            return Err(Error::Decode(format!(
+                "page of {} bytes is too small to hold the number of offsets",
+                buffer.len()
+            )));
+        }
         let offset_containing_number_of_offsets =
             buffer.len() - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS;
         let number_of_offsets =
             byteorder::LittleEndian::read_u16(&buffer[offset_containing_number_of_offsets..])
                 as usize;
         if number_of_offsets == 0 {
-            return BufferPage {
+            return Ok(BufferPage {
                 buffer,
                 starting_offsets: StartingOffsets::new(),
                 types: Fields::new(),
+                dictionary: Dictionary::new(),
                 current_write_offset: 0,
-            };
+            });
         }
+        let end_of_fields = offset_containing_number_of_offsets
+            .checked_sub(StartingOffsets::size_in_bytes_for(number_of_offsets))
+            .and_then(|offset| offset.checked_sub(Fields::size_in_bytes_for(number_of_offsets)))
+            .ok_or_else(|| {
+                Error::Corrupt(format!(
+                    "page of {} bytes cannot hold {} fields",
+                    buffer.len(),
+                    number_of_offsets
+                ))
+            })?;
 
         let starting_offsets = Self::decode_starting_offsets(&buffer, number_of_offsets);
-        let types = Self::decode_types(&buffer, number_of_offsets);
+        let types = Self::decode_types(&buffer, number_of_offsets)?;
+        Self::ensure_ascending_offsets(&starting_offsets, &types)?;
+        let dictionary = Self::decode_dictionary(&buffer, &types, end_of_fields)?;
+        let end_of_fields = end_of_fields - dictionary.size_in_bytes();
+        let last_starting_offset = *(starting_offsets.last_offset().unwrap()) as usize;
+        if last_starting_offset >= end_of_fields {
+            return Err(Error::Corrupt(format!(
+                "field offset {} lies outside the fields of the page",
+                last_starting_offset
+            )));
+        }
         let end_offset = types
             .last()
             .unwrap()
-            .end_offset_post_decode(&buffer, *(starting_offsets.last_offset().unwrap()) as usize);
+            .end_offset_post_decode(&buffer, last_starting_offset);
 
-        BufferPage {
+        Ok(BufferPage {
             buffer,
             starting_offsets,
             types,
+            dictionary,
             current_write_offset: end_offset,
+        })
+    }
+
+    pub(crate) fn try_decode_page(buffer: Vec<u8>) -> Result<BufferPage, Error> {
+        let layout = Self::try_decode_layout(&buffer)?;
+        Ok(BufferPage {
+            buffer,
+            starting_offsets: layout.starting_offsets,
+            types: layout.types,
+            dictionary: layout.dictionary,
+            current_write_offset: layout.current_write_offset,
+        })
+    }
+
+    /// Validates the page in `buffer` and decodes its footer, leaving the fields in place.
+    pub(crate) fn try_decode_layout(buffer: &[u8]) -> Result<PageLayout, Error> {
+        if buffer.len() < RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS {
+            return Err(Error::Corrupt(format!(
+                "page of {} bytes is too small to hold the number of offsets",
+                buffer.len()
+            )));
         }
+        let offset_containing_number_of_offsets =
+            buffer.len() - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS;
+        let number_of_offsets =
+            byteorder::LittleEndian::read_u16(&buffer[offset_containing_number_of_offsets..])
+                as usize;
+        let end_of_fields = offset_containing_number_of_offsets
+            .checked_sub(StartingOffsets::size_in_bytes_for(number_of_offsets))
+            .and_then(|offset| offset.checked_sub(Fields::size_in_bytes_for(number_of_offsets)))
+            .ok_or_else(|| {
+                Error::Corrupt(format!(
+                    "page of {} bytes cannot hold {} fields",
+                    buffer.len(),
+                    number_of_offsets
+                ))
+            })?;
+
+        let starting_offsets = Self::decode_starting_offsets(buffer, number_of_offsets);
+        let types = Self::decode_types(buffer, number_of_offsets)
+            .map_err(|error| Error::Corrupt(error.to_string()))?;
+        let dictionary = Self::decode_dictionary(buffer, &types, end_of_fields)?;
+        let end_of_fields = end_of_fields - dictionary.size_in_bytes();
+
+        let fields = &buffer[..end_of_fields];
+        let mut end_offset = 0;
+        for index in 0..number_of_offsets {
+            let starting_offset = starting_offsets.offset_value_at(index).unwrap() as usize;
+            if starting_offset < end_offset {
+                return Err(Error::Corrupt(format!(
+                    "field {} at offset {} overlaps the previous field",
+                    index, starting_offset
+                )));
+            }
+            let field_type = types.type_at(index).unwrap();
+            end_offset = field_type
+                .checked_end_offset(fields, starting_offset)
+                .ok_or_else(|| {
+                    Error::Corrupt(format!(
+                        "field {} at offset {} lies outside the fields of the page",
+                        index, starting_offset
+                    ))
+                })?;
+            if *field_type == FieldType::TypeDictStr
+                && dictionary
+                    .get(byteorder::LittleEndian::read_u16(
+                        &fields[starting_offset..],
+                    ))
+                    .is_none()
+            {
+                return Err(Error::Corrupt(format!(
+                    "field {} refers to a missing dictionary entry",
+                    index
+                )));
+            }
+        }
+
+        Ok(PageLayout {
+            starting_offsets,
+            types,
+            dictionary,
+            current_write_offset: end_offset,
+        })
+    }
+
+    // Only a null occupies no bytes, so it is the only field that may share its offset with the
+    // next one. With the offsets in order, checking the last one bounds all of them.
+    fn ensure_ascending_offsets(
+        starting_offsets: &StartingOffsets,
+        types: &Fields,
+    ) -> Result<(), Error> {
+        for index in 1..starting_offsets.length() {
+            let previous_offset = starting_offsets.offset_value_at(index - 1).unwrap();
+            let starting_offset = starting_offsets.offset_value_at(index).unwrap();
+            let previous_is_null = types.type_at(index - 1) == Some(&FieldType::TypeNull);
+            if starting_offset < previous_offset
+                || (starting_offset == previous_offset && !previous_is_null)
+            {
+                return Err(Error::Corrupt(format!(
+                    "field {} at offset {} does not follow the field at offset {}",
+                    index, starting_offset, previous_offset
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_dictionary(
+        buffer: &[u8],
+        types: &Fields,
+        end_of_types: usize,
+    ) -> Result<Dictionary, Error> {
+        if !types.contains(FieldType::TypeDictStr) {
+            return Ok(Dictionary::new());
+        }
+        Dictionary::decode_from(&buffer[..end_of_types])
     }
 
     fn decode_starting_offsets(buffer: &[u8], number_of_offsets: usize) -> StartingOffsets {
@@ -96,7 +266,7 @@ impl PageDecoder {
         )
     }
 
-    fn decode_types(buffer: &[u8], number_of_offsets: usize) -> Fields {
+    fn decode_types(buffer: &[u8], number_of_offsets: usize) -> Result<Fields, Error> {
         let number_of_types = number_of_offsets;
         let offset_containing_types = buffer.len()
             - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
@@ -112,16 +282,19 @@ impl PageDecoder {
 
 #[cfg(test)]
 mod tests {
+    use crate::buffer::dictionary::Dictionary;
     use crate::buffer::field_types::{FieldType, Fields};
+    use crate::buffer::page::BufferPage;
     use crate::buffer::page_encoder_decoder::{PageDecoder, PageEncoder};
+    use crate::error::Error;
     use crate::file::starting_offsets::StartingOffsets;
     use byteorder::ByteOrder;
 
     #[test]
     fn encode_and_decode_a_page() {
         let mut starting_offsets = StartingOffsets::new();
-        starting_offsets.add_offset(0);
-        starting_offsets.add_offset(2);
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(2).unwrap();
 
         let mut types = Fields::new();
         types.add(FieldType::TypeU16);
@@ -135,12 +308,129 @@ mod tests {
             buffer: &mut buffer,
             starting_offsets: &starting_offsets,
             types: &types,
+            dictionary: &Dictionary::new(),
         };
         encoder.encode();
 
-        let decoded = PageDecoder::decode_page(encoder.buffer.to_vec());
-        assert_eq!(2, decoded.starting_offsets.length());
+        let decoded = PageDecoder::decode_page(encoder.buffer.to_vec()).unwrap();
+        assert_eq!(2, decoded.len());
         assert_eq!(&FieldType::TypeU16, decoded.types.type_at(0).unwrap());
         assert_eq!(&FieldType::TypeU16, decoded.types.type_at(1).unwrap());
     }
+
+    #[test]
+    fn encode_and_decode_a_page_with_a_u32() {
+        let mut page = BufferPage::new(512);
+        page.add_u32(4_000_000_000).unwrap();
+
+        let decoded = PageDecoder::decode_page(page.encode().to_vec()).unwrap();
+        assert_eq!(&FieldType::TypeU32, decoded.types.type_at(0).unwrap());
+        assert_eq!(Some(4_000_000_000), decoded.get_u32(0).unwrap());
+
+        let decoded = PageDecoder::try_decode_page(page.encode().to_vec()).unwrap();
+        assert_eq!(Some(4_000_000_000), decoded.get_u32(0).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_decode_a_page_with_more_fields_than_it_can_hold() {
+        let mut buffer = vec![0; 64];
+        byteorder::LittleEndian::write_u16(&mut buffer[62..], 100);
+
+        assert!(matches!(
+            PageDecoder::decode_page(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn decode_a_page_with_consecutive_nulls() {
+        let mut page = BufferPage::new(512);
+        page.add_u16(250).unwrap();
+        page.add_null().unwrap();
+        page.add_null().unwrap();
+        page.add_u16(500).unwrap();
+
+        let decoded = PageDecoder::decode_page(page.encode().to_vec()).unwrap();
+        assert!(page == decoded);
+        assert_eq!(Some(500), decoded.get_u16(3).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_decode_a_page_with_offsets_out_of_order() {
+        let mut page = BufferPage::new(64);
+        page.add_u16(250).unwrap();
+        page.add_u16(500).unwrap();
+        page.add_u16(750).unwrap();
+        let mut buffer = page.encode().to_vec();
+        let offset_of_second_offset = 64 - 2 - 2 * 4;
+        byteorder::LittleEndian::write_u32(&mut buffer[offset_of_second_offset..], 0);
+
+        assert!(matches!(
+            PageDecoder::decode_page(buffer.clone()),
+            Err(Error::Corrupt(_))
+        ));
+        byteorder::LittleEndian::write_u32(&mut buffer[offset_of_second_offset..], 6);
+        assert!(matches!(
+            PageDecoder::decode_page(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn try_decode_a_valid_page() {
+        let mut page = BufferPage::new(512);
+        page.add_string("RocksDB").unwrap();
+        page.add_u16(250).unwrap();
+        page.add_bytes(b"BoltDB".to_vec()).unwrap();
+
+        let decoded = PageDecoder::try_decode_page(page.encode().to_vec()).unwrap();
+        assert!(page == decoded);
+        assert_eq!(page.current_write_offset, decoded.current_write_offset);
+    }
+
+    #[test]
+    fn attempt_to_try_decode_a_page_with_a_field_beyond_the_fields() {
+        let mut page = BufferPage::new(64);
+        page.add_bytes(b"RocksDB".to_vec()).unwrap();
+        let mut buffer = page.encode().to_vec();
+        byteorder::LittleEndian::write_u16(&mut buffer[0..2], 1000);
+
+        assert!(matches!(
+            PageDecoder::try_decode_page(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn try_decode_a_page_with_dictionary_encoded_strings() {
+        let mut page = BufferPage::new(512);
+        page.add_dict_string("RocksDB").unwrap();
+        page.add_u16(250).unwrap();
+        page.add_dict_string("RocksDB").unwrap();
+
+        let decoded = PageDecoder::try_decode_page(page.encode().to_vec()).unwrap();
+        assert!(page == decoded);
+        assert_eq!(Some("RocksDB"), decoded.get_string(2).unwrap());
+    }
+
+    #[test]
+    fn try_decode_random_buffers_without_panicking() {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next_random = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..10_000 {
+            let mut buffer: Vec<u8> = (0..64).map(|_| next_random() as u8).collect();
+            if next_random() % 2 == 0 {
+                byteorder::LittleEndian::write_u16(&mut buffer[62..], (next_random() % 8) as u16);
+            }
+            if let Ok(page) = PageDecoder::try_decode_page(buffer) {
+                assert_eq!(page.len(), page.iter_fields().count());
+            }
+        }
+    }
 }
@@ -0,0 +1,207 @@
+use crate::assert_borrowed_type;
+use crate::buffer::dictionary::Dictionary;
+use crate::buffer::field_types::{FieldType, FieldValue, Fields};
+use crate::buffer::page::decode_field;
+use crate::buffer::page_encoder_decoder::PageDecoder;
+use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::{EncoderDecoder, U16EncoderDecoder, U32EncoderDecoder, U8EncoderDecoder};
+use crate::error::Error;
+use crate::file::starting_offsets::StartingOffsets;
+
+/// A read-only page decoded over a borrowed buffer, e.g. a block of a memory mapped file,
+/// so reading its fields does not copy the block.
+pub(crate) struct BufferPageRef<'a> {
+    buffer: &'a [u8],
+    starting_offsets: StartingOffsets,
+    types: Fields,
+    dictionary: Dictionary,
+}
+
+impl<'a> BufferPageRef<'a> {
+    pub(crate) fn decode_from(buffer: &'a [u8]) -> Result<Self, Error> {
+        let layout = PageDecoder::try_decode_layout(buffer)?;
+        Ok(BufferPageRef {
+            buffer,
+            starting_offsets: layout.starting_offsets,
+            types: layout.types,
+            dictionary: layout.dictionary,
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.starting_offsets.length()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn is_null(&self, index: usize) -> bool {
+        self.types.type_at(index) == Some(&FieldType::TypeNull)
+    }
+
+    pub(crate) fn field(&self, index: usize) -> Option<FieldValue> {
+        let starting_offset = self.starting_offsets.offset_value_at(index)? as usize;
+        decode_field(
+            self.buffer,
+            self.types.type_at(index)?,
+            starting_offset,
+            &self.dictionary,
+        )
+    }
+
+    pub(crate) fn iter_fields(&self) -> impl Iterator<Item = FieldValue> + '_ {
+        (0..self.len()).filter_map(|index| self.field(index))
+    }
+
+    pub(crate) fn get_u8(&self, index: usize) -> Result<Option<u8>, Error> {
+        Ok(self
+            .starting_offset_of(index, FieldType::TypeU8)?
+            .map(|starting_offset| *U8EncoderDecoder.decode(self.buffer, starting_offset).0))
+    }
+
+    pub(crate) fn get_u16(&self, index: usize) -> Result<Option<u16>, Error> {
+        Ok(self
+            .starting_offset_of(index, FieldType::TypeU16)?
+            .map(|starting_offset| *U16EncoderDecoder.decode(self.buffer, starting_offset).0))
+    }
+
+    pub(crate) fn get_u32(&self, index: usize) -> Result<Option<u32>, Error> {
+        Ok(self
+            .starting_offset_of(index, FieldType::TypeU32)?
+            .map(|starting_offset| *U32EncoderDecoder.decode(self.buffer, starting_offset).0))
+    }
+
+    pub(crate) fn get_bytes(&self, index: usize) -> Result<Option<&'a [u8]>, Error> {
+        Ok(self
+            .starting_offset_of(index, FieldType::TypeBytes)?
+            .map(|starting_offset| self.bytes_at(starting_offset)))
+    }
+
+    pub(crate) fn get_str(&self, index: usize) -> Result<Option<&str>, Error> {
+        if self.types.type_at(index) == Some(&FieldType::TypeDictStr) {
+            let id = self
+                .starting_offset_of(index, FieldType::TypeDictStr)?
+                .map(|starting_offset| *U16EncoderDecoder.decode(self.buffer, starting_offset).0);
+            return Ok(id.and_then(|id| self.dictionary.get(id)));
+        }
+        match self.starting_offset_of(index, FieldType::TypeString)? {
+            Some(starting_offset) => std::str::from_utf8(self.bytes_at(starting_offset))
+                .map(Some)
+                .map_err(|_| Error::Decode(format!("field at index {} is not valid UTF-8", index))),
+            None => Ok(None),
+        }
+    }
+
+    fn bytes_at(&self, starting_offset: usize) -> &'a [u8] {
+        assert_borrowed_type(BytesEncoderDecoder.decode(self.buffer, starting_offset).0)
+    }
+
+    fn starting_offset_of(
+        &self,
+        index: usize,
+        expected: FieldType,
+    ) -> Result<Option<usize>, Error> {
+        match self.types.type_at(index) {
+            Some(FieldType::TypeNull) => Ok(None),
+            Some(field_type) if *field_type != expected => Err(Error::TypeMismatch { index }),
+            _ => Ok(self
+                .starting_offsets
+                .offset_value_at(index)
+                .map(|starting_offset| starting_offset as usize)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::field_types::FieldValue;
+    use crate::buffer::page::BufferPage;
+    use crate::buffer::page_ref::BufferPageRef;
+    use crate::error::Error;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    #[test]
+    fn decode_over_a_borrowed_buffer_and_read_the_fields() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(10).unwrap();
+        page.add_u16(250).unwrap();
+        page.add_u32(70_000).unwrap();
+        page.add_bytes(b"RocksDB".to_vec()).unwrap();
+        page.add_string("LSM-based storage engine").unwrap();
+        page.add_dict_string("LSM").unwrap();
+        page.add_null().unwrap();
+        let encoded = page.encode().to_vec();
+
+        let page = BufferPageRef::decode_from(&encoded).unwrap();
+        assert_eq!(7, page.len());
+        assert!(!page.is_empty());
+        assert_eq!(Some(10), page.get_u8(0).unwrap());
+        assert_eq!(Some(250), page.get_u16(1).unwrap());
+        assert_eq!(Some(70_000), page.get_u32(2).unwrap());
+        assert_eq!(Some(&b"RocksDB"[..]), page.get_bytes(3).unwrap());
+        assert_eq!(Some("LSM-based storage engine"), page.get_str(4).unwrap());
+        assert_eq!(Some("LSM"), page.get_str(5).unwrap());
+        assert_eq!(None, page.get_u16(6).unwrap());
+        assert!(page.is_null(6));
+    }
+
+    #[test]
+    fn borrow_bytes_from_the_underlying_buffer() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_bytes(b"RocksDB".to_vec()).unwrap();
+        let encoded = page.encode().to_vec();
+
+        let bytes = BufferPageRef::decode_from(&encoded)
+            .unwrap()
+            .get_bytes(0)
+            .unwrap()
+            .unwrap();
+        assert!(encoded.as_ptr_range().contains(&bytes.as_ptr()));
+    }
+
+    #[test]
+    fn iterate_over_the_same_fields_as_the_owned_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(250).unwrap();
+        page.add_string("RocksDB").unwrap();
+        page.add_char('🦀').unwrap();
+        let encoded = page.encode().to_vec();
+
+        let page_ref = BufferPageRef::decode_from(&encoded).unwrap();
+        assert_eq!(
+            vec![
+                FieldValue::U16(250),
+                FieldValue::Str("RocksDB".to_string()),
+                FieldValue::Char('🦀')
+            ],
+            page_ref.iter_fields().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            page.iter_fields().collect::<Vec<_>>(),
+            page_ref.iter_fields().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn attempt_to_read_a_field_with_a_different_type() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(250).unwrap();
+        let encoded = page.encode().to_vec();
+
+        let page = BufferPageRef::decode_from(&encoded).unwrap();
+        assert!(matches!(
+            page.get_bytes(0),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_over_a_corrupted_buffer() {
+        assert!(matches!(
+            BufferPageRef::decode_from(&[0xFF; 8]),
+            Err(Error::Corrupt(_))
+        ));
+    }
+}
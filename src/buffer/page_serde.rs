@@ -0,0 +1,70 @@
+use crate::buffer::field_types::FieldValue;
+use crate::buffer::page::BufferPage;
+use serde::de::Error;
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for BufferPage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut sequence = serializer.serialize_seq(Some(self.len()))?;
+        for field in self.iter_fields() {
+            sequence.serialize_element(&field)?;
+        }
+        sequence.end()
+    }
+}
+
+impl BufferPage {
+    pub(crate) fn deserialize_with_block_size<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        block_size: usize,
+    ) -> Result<BufferPage, D::Error> {
+        let fields = Vec::<FieldValue>::deserialize(deserializer)?;
+        let mut page = BufferPage::new(block_size);
+        for field in fields {
+            page.add_field_value(field).map_err(D::Error::custom)?;
+        }
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::field_types::FieldValue;
+    use crate::buffer::page::BufferPage;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    #[test]
+    fn serialize_and_deserialize_a_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(250).unwrap();
+        page.add_string("PebbleDB is an LSM-based storage engine")
+            .unwrap();
+        page.add_bytes(b"RocksDB".to_vec()).unwrap();
+        page.add_u16(500).unwrap();
+        page.add_u32(310).unwrap();
+
+        let json = serde_json::to_string(&page).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let mut deserialized =
+            BufferPage::deserialize_with_block_size(&mut deserializer, BLOCK_SIZE).unwrap();
+
+        assert_eq!(
+            page.iter_fields().collect::<Vec<FieldValue>>(),
+            deserialized.iter_fields().collect::<Vec<FieldValue>>()
+        );
+        assert_eq!(page.encode(), deserialized.encode());
+    }
+
+    #[test]
+    fn attempt_to_deserialize_fields_that_do_not_fit_in_the_block() {
+        let json = serde_json::to_string(&vec![FieldValue::U32(310); 20]).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+
+        let error = BufferPage::deserialize_with_block_size(&mut deserializer, 32)
+            .err()
+            .unwrap();
+        assert_eq!("Page does not have enough space", error.to_string());
+    }
+}
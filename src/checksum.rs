@@ -0,0 +1,51 @@
+const CRC32_POLYNOMIAL: u32 = 0xEDB88320;
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC32_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checksum::crc32;
+
+    #[test]
+    fn crc32_of_empty_bytes() {
+        assert_eq!(0, crc32(b""));
+    }
+
+    #[test]
+    fn crc32_of_check_bytes() {
+        assert_eq!(0xCBF43926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn crc32_differs_for_different_bytes() {
+        assert_ne!(
+            crc32(b"RocksDB is an LSM-based storage engine"),
+            crc32(b"RocksDB is an LSM-based storage enginf")
+        );
+    }
+}
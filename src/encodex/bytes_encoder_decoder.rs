@@ -6,6 +6,14 @@ pub(crate) struct BytesEncoderDecoder;
 
 impl BytesEncoderDecoder {
     pub(crate) const RESERVED_SIZE_FOR_BYTE_SLICE: usize = size_of::<u16>();
+    /// The length of a slice is encoded as a u16, so longer slices cannot be encoded; callers
+    /// must check against this limit, `encode` would otherwise silently truncate the length.
+    pub(crate) const MAX_LENGTH: usize = u16::MAX as usize;
+
+    pub(crate) fn decode_owned(&self, encoded: &[u8], from_offset: usize) -> (Vec<u8>, EndOffset) {
+        let (decoded, end_offset) = self.decode(encoded, from_offset);
+        (decoded.into_owned(), end_offset)
+    }
 }
 
 impl EncoderDecoder<[u8]> for BytesEncoderDecoder {
@@ -88,6 +96,20 @@ mod tests {
         assert_eq!(&decoded[..], &source[..]);
     }
 
+    #[test]
+    fn decode_owned_bytes_matching_the_borrowed_bytes() {
+        let source = b"Rocks is LSM-based";
+        let mut destination = vec![0; 100];
+        let _ = BytesEncoderDecoder.encode(&source[..], &mut destination, 10);
+
+        let (borrowed, borrowed_end_offset) = BytesEncoderDecoder.decode(&destination, 10);
+        let (owned, owned_end_offset) = BytesEncoderDecoder.decode_owned(&destination, 10);
+
+        assert_eq!(&borrowed[..], &owned[..]);
+        assert_eq!(borrowed_end_offset, owned_end_offset);
+        assert_eq!(source.to_vec(), owned);
+    }
+
     #[test]
     fn encode_decode_bytes_at_a_different_offset() {
         let source = b"Rocks is LSM-based";
@@ -0,0 +1,66 @@
+use crate::encodex::{BytesNeededForEncoding, EndOffset};
+use crate::error::Error;
+use byteorder::ByteOrder;
+
+/// Stores a char as its u32 scalar value. Unlike [`crate::encodex::EncoderDecoder`],
+/// decoding is fallible because not every u32 is a valid char (e.g. surrogates).
+pub(crate) struct CharEncoderDecoder;
+
+impl CharEncoderDecoder {
+    pub(crate) const SIZE: usize = size_of::<u32>();
+
+    pub(crate) fn encode(
+        &self,
+        source: char,
+        destination: &mut [u8],
+        destination_starting_offset: usize,
+    ) -> BytesNeededForEncoding {
+        byteorder::LittleEndian::write_u32(
+            &mut destination[destination_starting_offset..],
+            source as u32,
+        );
+        Self::SIZE
+    }
+
+    pub(crate) fn decode(
+        &self,
+        encoded: &[u8],
+        from_offset: usize,
+    ) -> Result<(char, EndOffset), Error> {
+        let scalar = byteorder::LittleEndian::read_u32(&encoded[from_offset..]);
+        let decoded = char::from_u32(scalar).ok_or_else(|| {
+            Error::Decode(format!("{:#x} is not a valid unicode scalar value", scalar))
+        })?;
+        Ok((decoded, from_offset + Self::SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encodex::char_encoder_decoder::CharEncoderDecoder;
+    use crate::error::Error;
+    use byteorder::ByteOrder;
+
+    #[test]
+    fn encode_decode_an_emoji() {
+        let mut buffer = vec![0; 100];
+        let bytes_needed = CharEncoderDecoder.encode('🦀', &mut buffer, 10);
+
+        assert_eq!(4, bytes_needed);
+
+        let (decoded, end_offset) = CharEncoderDecoder.decode(&buffer, 10).unwrap();
+        assert_eq!('🦀', decoded);
+        assert_eq!(14, end_offset);
+    }
+
+    #[test]
+    fn attempt_to_decode_a_surrogate() {
+        let mut buffer = vec![0; 4];
+        byteorder::LittleEndian::write_u32(&mut buffer, 0xD800);
+
+        assert!(matches!(
+            CharEncoderDecoder.decode(&buffer, 0),
+            Err(Error::Decode(_))
+        ));
+    }
+}
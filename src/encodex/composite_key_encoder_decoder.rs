@@ -0,0 +1,209 @@
+use crate::encodex::{BytesNeededForEncoding, EndOffset};
+use crate::error::Error;
+
+const ESCAPE: u8 = 0x00;
+const ESCAPED_ZERO: u8 = 0xFF;
+const END_OF_COMPONENT: u8 = 0x01;
+const END_OF_KEY: u8 = 0x00;
+
+// Encodes the components of a key so that the encoded bytes compare in the same order as the
+// components do, one by one: every 0x00 inside a component is escaped as 0x00 0xFF, each
+// component ends with 0x00 0x01 and the key ends with 0x00 0x00. Both markers sort before any
+// escaped or regular byte, so a shorter component (or a key with fewer components) sorts first.
+// Unlike `EncoderDecoder`, decoding is fallible because the input may be truncated or carry an
+// unknown marker.
+pub(crate) struct CompositeKeyEncoderDecoder;
+
+impl CompositeKeyEncoderDecoder {
+    pub(crate) fn bytes_needed_for_encoding(&self, source: &[Vec<u8>]) -> BytesNeededForEncoding {
+        source
+            .iter()
+            .map(|component| {
+                component.len() + component.iter().filter(|&&byte| byte == ESCAPE).count() + 2
+            })
+            .sum::<usize>()
+            + 2
+    }
+
+    pub(crate) fn encode(
+        &self,
+        source: &[Vec<u8>],
+        destination: &mut [u8],
+        destination_starting_offset: usize,
+    ) -> BytesNeededForEncoding {
+        let mut offset = destination_starting_offset;
+        let mut write = |byte: u8| {
+            destination[offset] = byte;
+            offset += 1;
+        };
+        for component in source {
+            for &byte in component {
+                write(byte);
+                if byte == ESCAPE {
+                    write(ESCAPED_ZERO);
+                }
+            }
+            write(ESCAPE);
+            write(END_OF_COMPONENT);
+        }
+        write(ESCAPE);
+        write(END_OF_KEY);
+        offset - destination_starting_offset
+    }
+
+    pub(crate) fn decode(
+        &self,
+        encoded: &[u8],
+        from_offset: usize,
+    ) -> Result<(Vec<Vec<u8>>, EndOffset), Error> {
+        let byte_at = |offset: usize| {
+            encoded.get(offset).copied().ok_or_else(|| {
+                Error::Decode(format!(
+                    "composite key starting at offset {} ends before its end marker",
+                    from_offset
+                ))
+            })
+        };
+        let mut components = Vec::new();
+        let mut component = Vec::new();
+        let mut offset = from_offset;
+        loop {
+            let byte = byte_at(offset)?;
+            offset += 1;
+            if byte != ESCAPE {
+                component.push(byte);
+                continue;
+            }
+            let marker = byte_at(offset)?;
+            offset += 1;
+            match marker {
+                ESCAPED_ZERO => component.push(ESCAPE),
+                END_OF_COMPONENT => components.push(std::mem::take(&mut component)),
+                END_OF_KEY => return Ok((components, offset)),
+                _ => {
+                    return Err(Error::Decode(format!(
+                        "unknown marker {} in composite key",
+                        marker
+                    )))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encodex::composite_key_encoder_decoder::CompositeKeyEncoderDecoder;
+    use crate::error::Error;
+
+    fn encode(components: &[&[u8]]) -> Vec<u8> {
+        let components: Vec<Vec<u8>> = components
+            .iter()
+            .map(|component| component.to_vec())
+            .collect();
+        let mut encoded =
+            vec![0; CompositeKeyEncoderDecoder.bytes_needed_for_encoding(&components)];
+        let bytes_needed = CompositeKeyEncoderDecoder.encode(&components, &mut encoded, 0);
+        assert_eq!(encoded.len(), bytes_needed);
+        encoded
+    }
+
+    fn decode(encoded: &[u8]) -> Vec<Vec<u8>> {
+        let (decoded, end_offset) = CompositeKeyEncoderDecoder.decode(encoded, 0).unwrap();
+        assert_eq!(encoded.len(), end_offset);
+        decoded
+    }
+
+    #[test]
+    fn encode_decode_a_composite_key() {
+        let encoded = encode(&[b"tenant-1", b"42"]);
+        assert_eq!(vec![b"tenant-1".to_vec(), b"42".to_vec()], decode(&encoded));
+    }
+
+    #[test]
+    fn encode_decode_components_containing_the_separator() {
+        let encoded = encode(&[&[0, 1, 0], &[0xFF, 0]]);
+        assert_eq!(vec![vec![0, 1, 0], vec![0xFF, 0]], decode(&encoded));
+    }
+
+    #[test]
+    fn encode_decode_empty_components() {
+        let encoded = encode(&[b"", b"RocksDB", b""]);
+        assert_eq!(
+            vec![b"".to_vec(), b"RocksDB".to_vec(), b"".to_vec()],
+            decode(&encoded)
+        );
+        assert!(decode(&encode(&[])).is_empty());
+    }
+
+    #[test]
+    fn encode_decode_a_composite_key_at_an_offset() {
+        let components = vec![b"tenant".to_vec(), vec![0]];
+        let mut encoded = vec![0xAA; 3];
+        encoded.resize(
+            3 + CompositeKeyEncoderDecoder.bytes_needed_for_encoding(&components),
+            0,
+        );
+        CompositeKeyEncoderDecoder.encode(&components, &mut encoded, 3);
+
+        let (decoded, end_offset) = CompositeKeyEncoderDecoder.decode(&encoded, 3).unwrap();
+        assert_eq!(components, decoded);
+        assert_eq!(encoded.len(), end_offset);
+    }
+
+    #[test]
+    fn attempt_to_decode_a_composite_key_with_an_unknown_marker() {
+        assert!(matches!(
+            CompositeKeyEncoderDecoder.decode(&[b'a', 0, 7, 0, 0], 0),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_a_truncated_composite_key() {
+        let encoded = encode(&[b"tenant-1", &[0]]);
+        (0..encoded.len()).for_each(|length| {
+            assert!(matches!(
+                CompositeKeyEncoderDecoder.decode(&encoded[..length], 0),
+                Err(Error::Decode(_))
+            ));
+        });
+        assert!(matches!(
+            CompositeKeyEncoderDecoder.decode(&encoded, encoded.len() + 1),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn encoding_preserves_the_order_of_components() {
+        let keys: Vec<&[&[u8]]> = vec![
+            &[],
+            &[b""],
+            &[b"", b"a"],
+            &[b"a"],
+            &[b"a", b""],
+            &[b"a", b"b"],
+            &[&[b'a', 0]],
+            &[&[b'a', 0], b""],
+            &[&[b'a', 0, 0]],
+            &[&[b'a', 1]],
+            &[b"ab"],
+            &[&[b'a', 0xFF]],
+            &[b"b"],
+        ];
+        keys.windows(2).for_each(|pair| {
+            assert!(
+                pair[0] < pair[1],
+                "{:?} is not less than {:?}",
+                pair[0],
+                pair[1]
+            );
+            assert!(
+                encode(pair[0]) < encode(pair[1]),
+                "encoding of {:?} is not less than encoding of {:?}",
+                pair[0],
+                pair[1]
+            );
+        });
+    }
+}
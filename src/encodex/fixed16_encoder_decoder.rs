@@ -0,0 +1,51 @@
+use crate::encodex::{BytesNeededForEncoding, EncoderDecoder, EndOffset};
+use std::borrow::Cow;
+
+pub(crate) struct Fixed16EncoderDecoder;
+
+impl Fixed16EncoderDecoder {
+    pub(crate) const SIZE: usize = 16;
+}
+
+impl EncoderDecoder<[u8; 16]> for Fixed16EncoderDecoder {
+    fn bytes_needed_for_encoding(&self, _source: &[u8; 16]) -> BytesNeededForEncoding {
+        Self::SIZE
+    }
+
+    fn encode(
+        &self,
+        source: &[u8; 16],
+        destination: &mut [u8],
+        destination_starting_offset: usize,
+    ) -> BytesNeededForEncoding {
+        destination[destination_starting_offset..destination_starting_offset + Self::SIZE]
+            .copy_from_slice(source);
+        Self::SIZE
+    }
+
+    fn decode<'a>(&self, encoded: &'a [u8], from_offset: usize) -> (Cow<'a, [u8; 16]>, EndOffset) {
+        let end_offset = from_offset + Self::SIZE;
+        let decoded: &[u8; 16] = encoded[from_offset..end_offset].try_into().unwrap();
+        (Cow::Borrowed(decoded), end_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encodex::fixed16_encoder_decoder::Fixed16EncoderDecoder;
+    use crate::encodex::EncoderDecoder;
+
+    #[test]
+    fn encode_decode_16_bytes_without_a_length_prefix() {
+        let value: [u8; 16] = std::array::from_fn(|index| index as u8 + 1);
+        let mut buffer = vec![0; 100];
+        let bytes_needed = Fixed16EncoderDecoder.encode(&value, &mut buffer, 10);
+
+        assert_eq!(16, bytes_needed);
+        assert_eq!(&value, &buffer[10..26]);
+
+        let (decoded, end_offset) = Fixed16EncoderDecoder.decode(&buffer, 10);
+        assert_eq!(value, *decoded);
+        assert_eq!(26, end_offset);
+    }
+}
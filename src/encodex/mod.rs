@@ -2,7 +2,12 @@ use byteorder::ByteOrder;
 use std::borrow::Cow;
 
 pub(crate) mod bytes_encoder_decoder;
+pub(crate) mod char_encoder_decoder;
+pub(crate) mod composite_key_encoder_decoder;
+pub(crate) mod fixed16_encoder_decoder;
+pub(crate) mod raw;
 pub(crate) mod str_encoder_decoder;
+pub(crate) mod timestamp_encoder_decoder;
 
 pub(crate) type BytesNeededForEncoding = usize;
 pub(crate) type EndOffset = usize;
@@ -135,3 +140,25 @@ generate_fixed_size_numeric_encoder_decoder_tests!(
     u32_encoder_decoder_tests,
     U32EncoderDecoder
 );
+
+#[cfg(test)]
+mod numeric_encoder_decoder_tests {
+    use super::*;
+
+    fn round_trip<T: Copy + PartialEq + std::fmt::Debug, E: EncoderDecoder<T>>(
+        encoder: E,
+        value: T,
+    ) -> (T, EndOffset) {
+        let mut buffer = vec![0u8; 16];
+        encoder.encode(&value, &mut buffer, 3);
+        let (decoded, end_offset) = encoder.decode(&buffer, 3);
+        (decoded.into_owned(), end_offset)
+    }
+
+    #[test]
+    fn decode_every_numeric_type_through_the_same_generic_code() {
+        assert_eq!((250, 4), round_trip(U8EncoderDecoder, 250u8));
+        assert_eq!((60_000, 5), round_trip(U16EncoderDecoder, 60_000u16));
+        assert_eq!((70_000, 7), round_trip(U32EncoderDecoder, 70_000u32));
+    }
+}
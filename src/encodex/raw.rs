@@ -0,0 +1,114 @@
+use crate::assert_borrowed_type;
+use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::{EncoderDecoder, U16EncoderDecoder, U32EncoderDecoder, U8EncoderDecoder};
+use crate::error::Error;
+
+pub(crate) fn read_u8_at(buffer: &[u8], offset: usize) -> Result<u8, Error> {
+    ensure_within(buffer, offset, size_of::<u8>())?;
+    Ok(*U8EncoderDecoder.decode(buffer, offset).0)
+}
+
+pub(crate) fn read_u16_at(buffer: &[u8], offset: usize) -> Result<u16, Error> {
+    ensure_within(buffer, offset, size_of::<u16>())?;
+    Ok(*U16EncoderDecoder.decode(buffer, offset).0)
+}
+
+pub(crate) fn read_u32_at(buffer: &[u8], offset: usize) -> Result<u32, Error> {
+    ensure_within(buffer, offset, size_of::<u32>())?;
+    Ok(*U32EncoderDecoder.decode(buffer, offset).0)
+}
+
+pub(crate) fn read_bytes_at(buffer: &[u8], offset: usize) -> Result<&[u8], Error> {
+    let length = read_u16_at(buffer, offset)? as usize;
+    ensure_within(
+        buffer,
+        offset,
+        BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE + length,
+    )?;
+    Ok(assert_borrowed_type(
+        BytesEncoderDecoder.decode(buffer, offset).0,
+    ))
+}
+
+pub(crate) fn read_str_at(buffer: &[u8], offset: usize) -> Result<&str, Error> {
+    let bytes = read_bytes_at(buffer, offset)?;
+    std::str::from_utf8(bytes)
+        .map_err(|_| Error::Decode(format!("string at offset {} is not valid UTF-8", offset)))?;
+    Ok(assert_borrowed_type(
+        StrEncoderDecoder.decode(buffer, offset).0,
+    ))
+}
+
+fn ensure_within(buffer: &[u8], offset: usize, length: usize) -> Result<(), Error> {
+    match offset.checked_add(length) {
+        Some(end_offset) if end_offset <= buffer.len() => Ok(()),
+        _ => Err(Error::Decode(format!(
+            "{} bytes at offset {} lie outside the buffer of {} bytes",
+            length,
+            offset,
+            buffer.len()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+    use crate::encodex::raw::{read_bytes_at, read_str_at, read_u16_at, read_u32_at, read_u8_at};
+    use crate::encodex::{EncoderDecoder, U16EncoderDecoder, U32EncoderDecoder};
+    use crate::error::Error;
+
+    #[test]
+    fn read_numbers_at_valid_offsets() {
+        let mut buffer = vec![0; 16];
+        buffer[0] = 7;
+        U16EncoderDecoder.encode(&250, &mut buffer, 1);
+        U32EncoderDecoder.encode(&70_000, &mut buffer, 12);
+
+        assert_eq!(7, read_u8_at(&buffer, 0).unwrap());
+        assert_eq!(250, read_u16_at(&buffer, 1).unwrap());
+        assert_eq!(70_000, read_u32_at(&buffer, 12).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_read_numbers_at_out_of_range_offsets() {
+        let buffer = vec![0; 16];
+
+        assert!(matches!(read_u8_at(&buffer, 16), Err(Error::Decode(_))));
+        assert!(matches!(read_u16_at(&buffer, 15), Err(Error::Decode(_))));
+        assert!(matches!(read_u32_at(&buffer, 13), Err(Error::Decode(_))));
+        assert!(matches!(
+            read_u32_at(&buffer, usize::MAX),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn read_bytes_and_a_string_at_valid_offsets() {
+        let mut buffer = vec![0; 32];
+        BytesEncoderDecoder.encode(b"RocksDB", &mut buffer, 3);
+        BytesEncoderDecoder.encode(b"LSM", &mut buffer, 20);
+
+        assert_eq!(b"RocksDB", read_bytes_at(&buffer, 3).unwrap());
+        assert_eq!("LSM", read_str_at(&buffer, 20).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_read_bytes_running_past_the_buffer() {
+        let mut buffer = vec![0; 32];
+        U16EncoderDecoder.encode(&40, &mut buffer, 10);
+
+        assert!(matches!(read_bytes_at(&buffer, 10), Err(Error::Decode(_))));
+        assert!(matches!(read_bytes_at(&buffer, 31), Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn attempt_to_read_a_string_with_invalid_utf8() {
+        let mut buffer = vec![0; 32];
+        BytesEncoderDecoder.encode(&[0xFF, 0xFE], &mut buffer, 0);
+
+        assert_eq!(&[0xFF, 0xFE], read_bytes_at(&buffer, 0).unwrap());
+        assert!(matches!(read_str_at(&buffer, 0), Err(Error::Decode(_))));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::encodex::{BytesNeededForEncoding, EncoderDecoder, EndOffset};
+use byteorder::ByteOrder;
+use std::borrow::Cow;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub(crate) struct TimestampEncoderDecoder;
+
+impl TimestampEncoderDecoder {
+    const SIZE: usize = size_of::<i64>();
+
+    fn milliseconds_since_epoch(timestamp: &SystemTime) -> i64 {
+        match timestamp.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_millis() as i64,
+            Err(error) => -(error.duration().as_millis() as i64),
+        }
+    }
+
+    fn timestamp_from(milliseconds_since_epoch: i64) -> SystemTime {
+        let duration = Duration::from_millis(milliseconds_since_epoch.unsigned_abs());
+        if milliseconds_since_epoch >= 0 {
+            UNIX_EPOCH + duration
+        } else {
+            UNIX_EPOCH - duration
+        }
+    }
+}
+
+impl EncoderDecoder<SystemTime> for TimestampEncoderDecoder {
+    fn bytes_needed_for_encoding(&self, _source: &SystemTime) -> BytesNeededForEncoding {
+        Self::SIZE
+    }
+
+    fn encode(
+        &self,
+        source: &SystemTime,
+        destination: &mut [u8],
+        destination_starting_offset: usize,
+    ) -> BytesNeededForEncoding {
+        byteorder::LittleEndian::write_i64(
+            &mut destination[destination_starting_offset..],
+            Self::milliseconds_since_epoch(source),
+        );
+        Self::SIZE
+    }
+
+    fn decode<'a>(
+        &self,
+        encoded: &'a [u8],
+        from_offset: usize,
+    ) -> (Cow<'a, SystemTime>, EndOffset) {
+        let milliseconds_since_epoch = byteorder::LittleEndian::read_i64(&encoded[from_offset..]);
+        (
+            Cow::Owned(Self::timestamp_from(milliseconds_since_epoch)),
+            from_offset + Self::SIZE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encodex::timestamp_encoder_decoder::TimestampEncoderDecoder;
+    use crate::encodex::EncoderDecoder;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn encode_decode_a_timestamp_after_the_epoch() {
+        let timestamp = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let mut buffer = vec![0; 100];
+        let bytes_needed = TimestampEncoderDecoder.encode(&timestamp, &mut buffer, 10);
+
+        assert_eq!(8, bytes_needed);
+        assert_eq!(&1_700_000_000_123i64.to_le_bytes(), &buffer[10..18]);
+
+        let (decoded, end_offset) = TimestampEncoderDecoder.decode(&buffer, 10);
+        assert_eq!(timestamp, *decoded);
+        assert_eq!(18, end_offset);
+    }
+
+    #[test]
+    fn encode_decode_a_timestamp_before_the_epoch() {
+        let timestamp = UNIX_EPOCH - Duration::from_millis(86_400_000 * 365 + 42);
+        let mut buffer = vec![0; 8];
+        TimestampEncoderDecoder.encode(&timestamp, &mut buffer, 0);
+
+        assert_eq!(&(-(86_400_000i64 * 365 + 42)).to_le_bytes(), &buffer[..]);
+        assert_eq!(timestamp, *TimestampEncoderDecoder.decode(&buffer, 0).0);
+    }
+
+    #[test]
+    fn truncate_a_timestamp_to_milliseconds() {
+        let timestamp = UNIX_EPOCH + Duration::from_micros(1_500);
+        let mut buffer = vec![0; 8];
+        TimestampEncoderDecoder.encode(&timestamp, &mut buffer, 0);
+
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_millis(1),
+            *TimestampEncoderDecoder.decode(&buffer, 0).0
+        );
+    }
+}
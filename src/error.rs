@@ -0,0 +1,177 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    PageFull,
+    Corrupt(String),
+    TypeMismatch {
+        index: usize,
+    },
+    FieldNotFound {
+        index: usize,
+    },
+    BufferUnavailable,
+    Decode(String),
+    InvalidBlockSize {
+        block_size: usize,
+        minimum: usize,
+    },
+    ValueTooLarge {
+        length: usize,
+        maximum: usize,
+    },
+    BlockSizeMismatch {
+        file_name: String,
+        block_size: usize,
+        expected: usize,
+    },
+    RecordTooLarge {
+        length: usize,
+        block_size: usize,
+    },
+    OffsetTooLarge {
+        offset: usize,
+        maximum: usize,
+    },
+    InvalidBloomFilter {
+        number_of_bits: usize,
+        number_of_hashes: u8,
+    },
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(err) => err,
+            other => io::Error::other(other),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(formatter, "I/O error: {}", err),
+            Error::PageFull => write!(formatter, "Page does not have enough space"),
+            Error::Corrupt(reason) => write!(formatter, "Corrupt data: {}", reason),
+            Error::TypeMismatch { index } => {
+                write!(formatter, "Type mismatch for the field at index {}", index)
+            }
+            Error::FieldNotFound { index } => {
+                write!(formatter, "No field exists at index {}", index)
+            }
+            Error::BufferUnavailable => write!(formatter, "Buffer is unavailable"),
+            Error::Decode(reason) => write!(formatter, "Decode error: {}", reason),
+            Error::InvalidBlockSize {
+                block_size,
+                minimum,
+            } => write!(
+                formatter,
+                "Block size {} is smaller than the minimum of {}",
+                block_size, minimum
+            ),
+            Error::ValueTooLarge { length, maximum } => write!(
+                formatter,
+                "Value of {} bytes exceeds the maximum of {} bytes",
+                length, maximum
+            ),
+            Error::BlockSizeMismatch {
+                file_name,
+                block_size,
+                expected,
+            } => write!(
+                formatter,
+                "File {} was created with a block size of {} but is opened with {}",
+                file_name, expected, block_size
+            ),
+            Error::RecordTooLarge { length, block_size } => write!(
+                formatter,
+                "Record of {} bytes does not fit in an empty log block of {} bytes",
+                length, block_size
+            ),
+            Error::OffsetTooLarge { offset, maximum } => write!(
+                formatter,
+                "Offset {} exceeds the maximum encodable offset of {}",
+                offset, maximum
+            ),
+            Error::InvalidBloomFilter {
+                number_of_bits,
+                number_of_hashes,
+            } => write!(
+                formatter,
+                "Bloom filter needs at least one bit and one hash, got {} bits and {} hashes",
+                number_of_bits, number_of_hashes
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use std::io;
+
+    #[test]
+    fn convert_an_io_error() {
+        let error: Error = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert!(matches!(error, Error::Io(ref err) if err.kind() == io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn convert_into_an_io_error() {
+        let error: io::Error = Error::PageFull.into();
+        assert_eq!(io::ErrorKind::Other, error.kind());
+        assert_eq!("Page does not have enough space", error.to_string());
+    }
+
+    #[test]
+    fn display_an_io_error() {
+        let error = Error::Io(io::Error::other("disk failure"));
+        assert_eq!("I/O error: disk failure", error.to_string());
+    }
+
+    #[test]
+    fn display_a_buffer_unavailable_error() {
+        assert_eq!(
+            "Buffer is unavailable",
+            Error::BufferUnavailable.to_string()
+        );
+    }
+
+    #[test]
+    fn display_a_type_mismatch_error() {
+        assert_eq!(
+            "Type mismatch for the field at index 2",
+            Error::TypeMismatch { index: 2 }.to_string()
+        );
+    }
+
+    #[test]
+    fn display_a_record_too_large_error() {
+        assert_eq!(
+            "Record of 200 bytes does not fit in an empty log block of 100 bytes",
+            Error::RecordTooLarge {
+                length: 200,
+                block_size: 100
+            }
+            .to_string()
+        );
+    }
+}
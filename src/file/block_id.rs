@@ -1,4 +1,5 @@
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct BlockId {
     file_name: String,
     pub(crate) block_number: usize,
@@ -27,11 +28,16 @@ impl BlockId {
             Some(Self::new(&self.file_name, self.block_number - 1))
         }
     }
+
+    pub(crate) fn next(&self) -> Self {
+        Self::new(&self.file_name, self.block_number + 1)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::file::block_id::BlockId;
+    use std::collections::{BTreeSet, HashMap};
 
     #[test]
     fn starting_offset_with_block_zero() {
@@ -66,4 +72,65 @@ mod tests {
         let previous_block_id = block_id.previous();
         assert_eq!(None, previous_block_id);
     }
+
+    #[test]
+    fn next_block_id() {
+        let block_id = BlockId::new("lsm.log", 0);
+
+        let next_block_id = block_id.next();
+        assert_eq!(BlockId::new("lsm.log", 1), next_block_id);
+    }
+
+    #[test]
+    fn previous_of_next_block_id() {
+        let block_id = BlockId::new("lsm.log", 3);
+
+        let previous_block_id = block_id.next().previous();
+        assert_eq!(Some(block_id), previous_block_id);
+    }
+
+    #[test]
+    fn block_ids_as_hash_map_keys() {
+        let mut buffer_index_by_block_id = HashMap::new();
+        buffer_index_by_block_id.insert(BlockId::new("lsm.log", 0), 0);
+        buffer_index_by_block_id.insert(BlockId::new("lsm.log", 1), 1);
+        buffer_index_by_block_id.insert(BlockId::new("btree.db", 0), 2);
+
+        assert_eq!(
+            Some(&0),
+            buffer_index_by_block_id.get(&BlockId::new("lsm.log", 0))
+        );
+        assert_eq!(
+            Some(&1),
+            buffer_index_by_block_id.get(&BlockId::new("lsm.log", 1))
+        );
+        assert_eq!(
+            Some(&2),
+            buffer_index_by_block_id.get(&BlockId::new("btree.db", 0))
+        );
+        assert_eq!(
+            None,
+            buffer_index_by_block_id.get(&BlockId::new("btree.db", 1))
+        );
+    }
+
+    #[test]
+    fn block_ids_are_ordered_by_file_name_and_then_block_number() {
+        let mut block_ids = BTreeSet::new();
+        block_ids.insert(BlockId::new("lsm.log", 2));
+        block_ids.insert(BlockId::new("btree.db", 5));
+        block_ids.insert(BlockId::new("lsm.log", 0));
+        block_ids.insert(BlockId::new("btree.db", 1));
+
+        let ordered: Vec<BlockId> = block_ids.into_iter().collect();
+        assert_eq!(
+            vec![
+                BlockId::new("btree.db", 1),
+                BlockId::new("btree.db", 5),
+                BlockId::new("lsm.log", 0),
+                BlockId::new("lsm.log", 2),
+            ],
+            ordered
+        );
+    }
 }
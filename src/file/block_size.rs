@@ -0,0 +1,49 @@
+use crate::error::Error;
+
+/// The smallest block that holds the page footer along with a single small record.
+pub(crate) const MINIMUM_BLOCK_SIZE: usize = 32;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct BlockSize(usize);
+
+impl BlockSize {
+    pub(crate) fn new(block_size: usize) -> Result<BlockSize, Error> {
+        if block_size < MINIMUM_BLOCK_SIZE {
+            return Err(Error::InvalidBlockSize {
+                block_size,
+                minimum: MINIMUM_BLOCK_SIZE,
+            });
+        }
+        Ok(BlockSize(block_size))
+    }
+
+    pub(crate) fn get(&self) -> usize {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::file::block_size::{BlockSize, MINIMUM_BLOCK_SIZE};
+
+    #[test]
+    fn create_a_block_size() {
+        assert_eq!(4096, BlockSize::new(4096).unwrap().get());
+        assert_eq!(
+            MINIMUM_BLOCK_SIZE,
+            BlockSize::new(MINIMUM_BLOCK_SIZE).unwrap().get()
+        );
+    }
+
+    #[test]
+    fn attempt_to_create_a_block_size_below_the_minimum() {
+        assert!(matches!(
+            BlockSize::new(MINIMUM_BLOCK_SIZE - 1),
+            Err(Error::InvalidBlockSize {
+                block_size,
+                minimum: MINIMUM_BLOCK_SIZE
+            }) if block_size == MINIMUM_BLOCK_SIZE - 1
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::error::Error;
+use byteorder::ByteOrder;
+
+const MAGIC: u32 = 0x6264_6370;
+
+/// Precedes the blocks of every file, recording the block size the file was created with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct FileHeader {
+    block_size: usize,
+}
+
+impl FileHeader {
+    pub(crate) const SIZE: usize = 2 * size_of::<u32>();
+
+    pub(crate) fn new(block_size: usize) -> Self {
+        FileHeader { block_size }
+    }
+
+    pub(crate) fn encode(&self) -> [u8; Self::SIZE] {
+        let mut encoded = [0; Self::SIZE];
+        byteorder::LittleEndian::write_u32(&mut encoded, MAGIC);
+        byteorder::LittleEndian::write_u32(
+            &mut encoded[size_of::<u32>()..],
+            self.block_size as u32,
+        );
+        encoded
+    }
+
+    pub(crate) fn decode_from(buffer: &[u8]) -> Result<Self, Error> {
+        if buffer.len() < Self::SIZE || byteorder::LittleEndian::read_u32(buffer) != MAGIC {
+            return Err(Error::Corrupt(
+                "file does not start with a valid file header".to_string(),
+            ));
+        }
+        Ok(FileHeader {
+            block_size: byteorder::LittleEndian::read_u32(&buffer[size_of::<u32>()..]) as usize,
+        })
+    }
+
+    pub(crate) fn validate(&self, file_name: &str, block_size: usize) -> Result<(), Error> {
+        if self.block_size != block_size {
+            return Err(Error::BlockSizeMismatch {
+                file_name: file_name.to_string(),
+                block_size,
+                expected: self.block_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::file::file_header::FileHeader;
+
+    #[test]
+    fn encode_and_decode_a_file_header() {
+        let header = FileHeader::new(4096);
+        let decoded = FileHeader::decode_from(&header.encode()).unwrap();
+
+        assert_eq!(header, decoded);
+        assert!(decoded.validate("btree.db", 4096).is_ok());
+    }
+
+    #[test]
+    fn attempt_to_validate_a_file_header_with_a_different_block_size() {
+        let header = FileHeader::new(4096);
+        assert!(matches!(
+            header.validate("btree.db", 512),
+            Err(Error::BlockSizeMismatch {
+                block_size: 512,
+                expected: 4096,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_a_file_header_without_the_magic_number() {
+        assert!(matches!(
+            FileHeader::decode_from(b"RocksDB!"),
+            Err(Error::Corrupt(_))
+        ));
+        assert!(matches!(
+            FileHeader::decode_from(&[0; 4]),
+            Err(Error::Corrupt(_))
+        ));
+    }
+}
@@ -1,100 +1,698 @@
+use crate::error::Error;
 use crate::file::block_id::BlockId;
+use crate::file::block_size::BlockSize;
+use crate::file::file_header::FileHeader;
+use crate::file::io_stats::{IoCounters, IoStats};
 use crate::page::Page;
-use std::cell::{RefCell, RefMut};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::Path;
+use std::io::{IoSlice, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 use std::{fs, io};
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum SyncMode {
+    Always,
+    Never,
+    Periodic(usize),
+}
+
 pub(crate) struct FileManager<PathType: AsRef<Path>> {
     directory: PathType,
     pub(crate) block_size: usize,
-    open_files: RefCell<HashMap<String, File>>,
+    sync_mode: SyncMode,
+    open_files: RwLock<HashMap<String, Arc<RwLock<File>>>>,
+    number_of_blocks_by_file: Mutex<HashMap<String, usize>>,
+    block_sizes_by_file: RwLock<HashMap<String, usize>>,
+    unsynced_writes_by_file: Mutex<HashMap<String, usize>>,
+    io_counters: IoCounters,
+    #[cfg(feature = "mmap")]
+    use_mmap: bool,
+    #[cfg(feature = "mmap")]
+    mappings: Mutex<HashMap<String, Arc<memmap2::Mmap>>>,
 }
 
-impl<PathType: AsRef<Path>> FileManager<PathType> {
-    pub(crate) fn new(directory: PathType, block_size: usize) -> Result<Self, io::Error> {
-        let exists = fs::metadata(directory.as_ref()).is_ok();
-        if !exists {
-            fs::create_dir(directory.as_ref())?
+pub(crate) struct FileManagerBuilder<PathType: AsRef<Path>> {
+    directory: PathType,
+    block_size: usize,
+    sync_mode: SyncMode,
+    create_dirs: bool,
+    #[cfg(feature = "mmap")]
+    use_mmap: bool,
+}
+
+impl<PathType: AsRef<Path>> FileManagerBuilder<PathType> {
+    pub(crate) const DEFAULT_BLOCK_SIZE: usize = 4096;
+
+    pub(crate) fn new(directory: PathType) -> Self {
+        FileManagerBuilder {
+            directory,
+            block_size: Self::DEFAULT_BLOCK_SIZE,
+            sync_mode: SyncMode::Always,
+            create_dirs: true,
+            #[cfg(feature = "mmap")]
+            use_mmap: true,
+        }
+    }
+
+    pub(crate) fn block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    pub(crate) fn sync_mode(mut self, sync_mode: SyncMode) -> Self {
+        self.sync_mode = sync_mode;
+        self
+    }
+
+    /// Creates the directory and its missing parents when set, which is the default;
+    /// otherwise the directory must already exist.
+    pub(crate) fn create_dirs(mut self, create_dirs: bool) -> Self {
+        self.create_dirs = create_dirs;
+        self
+    }
+
+    /// Reads blocks through memory mappings when set, which is the default.
+    #[cfg(feature = "mmap")]
+    pub(crate) fn mmap(mut self, use_mmap: bool) -> Self {
+        self.use_mmap = use_mmap;
+        self
+    }
+
+    pub(crate) fn build(self) -> Result<FileManager<PathType>, Error> {
+        let block_size = BlockSize::new(self.block_size)?.get();
+        let directory = self.directory;
+        if directory.as_ref().exists() && !directory.as_ref().is_dir() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                format!(
+                    "{} exists but is not a directory",
+                    directory.as_ref().display()
+                ),
+            )));
+        }
+        if self.create_dirs {
+            fs::create_dir_all(directory.as_ref())?;
+        } else if !directory.as_ref().exists() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} does not exist", directory.as_ref().display()),
+            )));
         }
         Ok(FileManager {
             directory,
             block_size,
-            open_files: RefCell::new(HashMap::new()),
+            sync_mode: self.sync_mode,
+            open_files: RwLock::new(HashMap::new()),
+            number_of_blocks_by_file: Mutex::new(HashMap::new()),
+            block_sizes_by_file: RwLock::new(HashMap::new()),
+            unsynced_writes_by_file: Mutex::new(HashMap::new()),
+            io_counters: IoCounters::default(),
+            #[cfg(feature = "mmap")]
+            use_mmap: self.use_mmap,
+            #[cfg(feature = "mmap")]
+            mappings: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl<PathType: AsRef<Path>> FileManager<PathType> {
+    pub(crate) fn new(directory: PathType, block_size: usize) -> Result<Self, Error> {
+        FileManagerBuilder::new(directory)
+            .block_size(block_size)
+            .build()
+    }
+
+    pub(crate) fn new_with_sync_mode(
+        directory: PathType,
+        block_size: usize,
+        sync_mode: SyncMode,
+    ) -> Result<Self, Error> {
+        FileManagerBuilder::new(directory)
+            .block_size(block_size)
+            .sync_mode(sync_mode)
+            .build()
+    }
+
+    pub(crate) fn use_block_size_for(
+        &self,
+        file_name: &str,
+        block_size: usize,
+    ) -> Result<(), Error> {
+        let block_size = BlockSize::new(block_size)?.get();
+        self.block_sizes_by_file
+            .write()
+            .unwrap()
+            .insert(file_name.to_string(), block_size);
+        self.number_of_blocks_by_file
+            .lock()
+            .unwrap()
+            .remove(file_name);
+        Ok(())
+    }
+
+    pub(crate) fn block_size_for(&self, file_name: &str) -> usize {
+        self.block_sizes_by_file
+            .read()
+            .unwrap()
+            .get(file_name)
+            .copied()
+            .unwrap_or(self.block_size)
+    }
+
+    pub(crate) fn read<T: Page>(&self, block_id: &BlockId) -> Result<T, Error> {
+        T::decode_from(self.read_whole_block(block_id)?)
+    }
+
+    pub(crate) fn read_verified<T: Page>(&self, block_id: &BlockId) -> Result<T, Error> {
+        T::decode_verified(self.read_whole_block(block_id)?)
+    }
+
+    pub(crate) fn read_into(&self, block_id: &BlockId, buffer: &mut [u8]) -> Result<usize, Error> {
+        let readable_length = buffer.len().min(self.block_size_for(block_id.file_name()));
+        self.with_file_for_read(block_id.file_name(), |file| {
+            if block_id.block_number >= self.number_of_blocks_in(block_id.file_name(), file)? {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "block {} does not exist in file {}",
+                        block_id.block_number,
+                        block_id.file_name()
+                    ),
+                )));
+            }
+            self.read_block(file, block_id, &mut buffer[..readable_length])
+        })
+    }
+
+    pub(crate) fn scan<F: FnMut(&BlockId, &[u8])>(
+        &self,
+        file_name: &str,
+        buffer: &mut [u8],
+        mut visit: F,
+    ) -> Result<usize, Error> {
+        let readable_length = buffer.len().min(self.block_size_for(file_name));
+        self.with_file_for_read(file_name, |file| {
+            let number_of_blocks = self.number_of_blocks_in(file_name, file)?;
+            let mut block_id = BlockId::new(file_name, 0);
+            while block_id.block_number < number_of_blocks {
+                let bytes_read =
+                    self.read_block(file, &block_id, &mut buffer[..readable_length])?;
+                visit(&block_id, &buffer[..bytes_read]);
+                block_id.block_number += 1;
+            }
+            Ok(number_of_blocks)
         })
     }
 
-    pub(crate) fn read<T: Page>(&self, block_id: &BlockId) -> Result<T, io::Error> {
-        let mut read_buffer = vec![0; self.block_size];
-        self.seek_and_run(block_id, |file| {
-            file.read(&mut read_buffer).map(|_number_of_bytes_read| ())
-        })?;
-        Ok(T::decode_from(read_buffer))
+    pub(crate) fn blocks<'a>(
+        &'a self,
+        file_name: &'a str,
+    ) -> Result<impl Iterator<Item = Result<Vec<u8>, Error>> + 'a, Error> {
+        let number_of_blocks = self.number_of_blocks(file_name)?;
+        Ok((0..number_of_blocks).map(move |block_number| {
+            let mut block = vec![0; self.block_size_for(file_name)];
+            self.read_into(&BlockId::new(file_name, block_number), &mut block)?;
+            Ok(block)
+        }))
     }
 
-    pub(crate) fn write(&self, block_id: &BlockId, data: &[u8]) -> Result<(), io::Error> {
-        self.seek_and_run(block_id, |file| {
+    pub(crate) fn write(&self, block_id: &BlockId, data: &[u8]) -> Result<(), Error> {
+        self.with_file(block_id.file_name(), |file| {
+            self.seek(file, block_id)?;
             file.write_all(data)?;
-            file.sync_data()
+            self.io_counters.record_write(data.len());
+            self.invalidate_mapping(block_id.file_name());
+            self.sync_as_per_mode(file, block_id.file_name())?;
+            self.extend_number_of_blocks(
+                block_id.file_name(),
+                block_id.starting_offset(self.block_size_for(block_id.file_name())) as usize
+                    + data.len(),
+            );
+            Ok(())
         })
     }
 
-    pub(crate) fn append_empty_block(&self, file_name: &str) -> Result<BlockId, io::Error> {
-        let block_id = BlockId::new(file_name, self.number_of_blocks(file_name)?);
-        let block_size = self.block_size;
+    pub(crate) fn write_page<T: Page>(
+        &self,
+        block_id: &BlockId,
+        page: &mut T,
+    ) -> Result<(), Error> {
+        self.write(block_id, page.encode())
+    }
 
-        self.seek_and_run(&block_id, |file| {
-            file.write_all(&vec![0; block_size])?;
-            file.sync_data()
-        })?;
+    pub(crate) fn write_vectored(&self, block_id: &BlockId, slices: &[&[u8]]) -> Result<(), Error> {
+        self.with_file(block_id.file_name(), |file| {
+            self.seek(file, block_id)?;
+            let mut io_slices: Vec<IoSlice> =
+                slices.iter().map(|slice| IoSlice::new(slice)).collect();
+            let mut io_slices = &mut io_slices[..];
+            while !io_slices.is_empty() {
+                match file.write_vectored(io_slices) {
+                    Ok(0) => {
+                        return Err(Error::Io(io::Error::new(
+                            io::ErrorKind::WriteZero,
+                            "failed to write the whole buffer",
+                        )))
+                    }
+                    Ok(bytes_written) => IoSlice::advance_slices(&mut io_slices, bytes_written),
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(Error::Io(err)),
+                }
+            }
+            let length: usize = slices.iter().map(|slice| slice.len()).sum();
+            self.io_counters.record_write(length);
+            self.invalidate_mapping(block_id.file_name());
+            self.sync_as_per_mode(file, block_id.file_name())?;
 
-        Ok(block_id)
+            self.extend_number_of_blocks(
+                block_id.file_name(),
+                block_id.starting_offset(self.block_size_for(block_id.file_name())) as usize
+                    + length,
+            );
+            Ok(())
+        })
     }
 
-    pub(crate) fn number_of_blocks(&self, file_name: &str) -> Result<usize, io::Error> {
-        let file = self.get_or_create(file_name)?;
+    pub(crate) fn append_empty_block(&self, file_name: &str) -> Result<BlockId, Error> {
+        self.append_block(file_name, &[])
+    }
+
+    pub(crate) fn append_block(&self, file_name: &str, data: &[u8]) -> Result<BlockId, Error> {
+        let block_size = self.block_size_for(file_name);
+        if data.len() > block_size {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "data of {} bytes does not fit in a block of {} bytes",
+                    data.len(),
+                    block_size
+                ),
+            )));
+        }
+        let mut block = vec![0; block_size];
+        block[..data.len()].copy_from_slice(data);
+
+        self.with_file(file_name, |file| {
+            let block_id = BlockId::new(file_name, self.number_of_blocks_in(file_name, file)?);
+
+            self.seek(file, &block_id)?;
+            file.write_all(&block)?;
+            self.io_counters.record_write(block.len());
+            self.invalidate_mapping(file_name);
+            self.sync_as_per_mode(file, file_name)?;
+            self.number_of_blocks_by_file
+                .lock()
+                .unwrap()
+                .insert(file_name.to_string(), block_id.block_number + 1);
+
+            Ok(block_id)
+        })
+    }
+
+    pub(crate) fn preallocate(
+        &self,
+        file_name: &str,
+        number_of_blocks: usize,
+    ) -> Result<(), Error> {
+        self.with_file(file_name, |file| {
+            if self.number_of_blocks_in(file_name, file)? >= number_of_blocks {
+                return Ok(());
+            }
+            file.set_len(
+                (FileHeader::SIZE + number_of_blocks * self.block_size_for(file_name)) as u64,
+            )?;
+            self.invalidate_mapping(file_name);
+            self.sync_as_per_mode(file, file_name)?;
+            self.number_of_blocks_by_file
+                .lock()
+                .unwrap()
+                .insert(file_name.to_string(), number_of_blocks);
+            Ok(())
+        })
+    }
+
+    /// Shrinks the file to its first `number_of_blocks` blocks; use `preallocate` to grow it.
+    pub(crate) fn truncate_to_blocks(
+        &self,
+        file_name: &str,
+        number_of_blocks: usize,
+    ) -> Result<(), Error> {
+        self.with_file(file_name, |file| {
+            let current_number_of_blocks = self.number_of_blocks_in(file_name, file)?;
+            if number_of_blocks > current_number_of_blocks {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "cannot truncate file {} of {} blocks to {} blocks",
+                        file_name, current_number_of_blocks, number_of_blocks
+                    ),
+                )));
+            }
+            file.set_len(
+                (FileHeader::SIZE + number_of_blocks * self.block_size_for(file_name)) as u64,
+            )?;
+            self.invalidate_mapping(file_name);
+            self.sync_as_per_mode(file, file_name)?;
+            self.number_of_blocks_by_file
+                .lock()
+                .unwrap()
+                .insert(file_name.to_string(), number_of_blocks);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn sync_file(&self, file_name: &str) -> Result<(), Error> {
+        self.with_file(file_name, |file| {
+            self.sync_data(file)?;
+            self.unsynced_writes_by_file
+                .lock()
+                .unwrap()
+                .remove(file_name);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn file_names(&self) -> Result<Vec<String>, Error> {
+        let mut file_names = Vec::new();
+        for entry in fs::read_dir(self.directory.as_ref())? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if let Some(file_name) = entry.file_name().to_str() {
+                file_names.push(file_name.to_string());
+            }
+        }
+        Ok(file_names)
+    }
+
+    pub(crate) fn delete_file(&self, file_name: &str) -> Result<(), Error> {
+        let path = self.directory.as_ref().join(Path::new(&file_name));
+        self.forget_file(file_name);
+        Ok(fs::remove_file(path)?)
+    }
+
+    /// Replaces the whole content of the file: the data is written and synced to a scratch
+    /// file which is then renamed over the file, so a crash leaves either the old or the new
+    /// content, never a torn mix of both.
+    pub(crate) fn write_atomic(&self, file_name: &str, data: &[u8]) -> Result<(), Error> {
+        let scratch_path = self.write_scratch_file(file_name, data)?;
+        self.forget_file(file_name);
+        fs::rename(
+            scratch_path,
+            self.directory.as_ref().join(Path::new(&file_name)),
+        )?;
+        #[cfg(unix)]
+        {
+            File::open(self.directory.as_ref())?.sync_all()?;
+            self.io_counters.record_sync();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn io_stats(&self) -> IoStats {
+        self.io_counters.snapshot()
+    }
+
+    pub(crate) fn number_of_blocks(&self, file_name: &str) -> Result<usize, Error> {
+        self.with_file(file_name, |file| self.number_of_blocks_in(file_name, file))
+    }
+
+    fn number_of_blocks_in(&self, file_name: &str, file: &File) -> Result<usize, Error> {
+        let mut number_of_blocks_by_file = self.number_of_blocks_by_file.lock().unwrap();
+        if let Some(number_of_blocks) = number_of_blocks_by_file.get(file_name) {
+            return Ok(*number_of_blocks);
+        }
+        let number_of_blocks = self.number_of_blocks_from_metadata(file_name, file)?;
+        number_of_blocks_by_file.insert(file_name.to_string(), number_of_blocks);
+        Ok(number_of_blocks)
+    }
+
+    fn number_of_blocks_from_metadata(&self, file_name: &str, file: &File) -> Result<usize, Error> {
         let metadata = file.metadata()?;
-        Ok(metadata.len() as usize / self.block_size) //TODO: validate
+        Ok(self.number_of_blocks_spanning(
+            file_name,
+            (metadata.len() as usize).saturating_sub(FileHeader::SIZE),
+        ))
+    }
+
+    fn extend_number_of_blocks(&self, file_name: &str, end_offset: usize) {
+        let mut number_of_blocks_by_file = self.number_of_blocks_by_file.lock().unwrap();
+        if let Some(number_of_blocks) = number_of_blocks_by_file.get_mut(file_name) {
+            *number_of_blocks =
+                (*number_of_blocks).max(self.number_of_blocks_spanning(file_name, end_offset));
+        }
+    }
+
+    fn number_of_blocks_spanning(&self, file_name: &str, length: usize) -> usize {
+        length.div_ceil(self.block_size_for(file_name))
+    }
+
+    fn seek(&self, file: &mut File, block_id: &BlockId) -> Result<(), io::Error> {
+        file.seek(SeekFrom::Start(self.file_offset_of(block_id)))?;
+        Ok(())
+    }
+
+    fn file_offset_of(&self, block_id: &BlockId) -> u64 {
+        (FileHeader::SIZE as i64
+            + block_id.starting_offset(self.block_size_for(block_id.file_name()))) as u64
     }
 
-    fn seek_and_run<Block: FnMut(&mut File) -> Result<(), io::Error>>(
+    fn with_file<T, Block: FnOnce(&mut File) -> Result<T, Error>>(
         &self,
-        block_id: &BlockId,
-        mut block: Block,
-    ) -> Result<(), io::Error> {
-        let block_size = self.block_size;
-        let mut file = self.get_or_create(block_id.file_name())?;
-        file.seek(SeekFrom::Start(block_id.starting_offset(block_size) as u64))?;
+        file_name: &str,
+        block: Block,
+    ) -> Result<T, Error> {
+        let file = self.get_or_create(file_name)?;
+        let mut file = file.write().unwrap();
         block(&mut file)
     }
 
-    fn get_or_create(&self, file_name: &str) -> Result<RefMut<'_, File>, io::Error> {
+    // Reads do not move the file cursor, so any number of them share the file.
+    fn with_file_for_read<T, Block: FnOnce(&File) -> Result<T, Error>>(
+        &self,
+        file_name: &str,
+        block: Block,
+    ) -> Result<T, Error> {
+        let file = self.get_or_create(file_name)?;
+        let file = file.read().unwrap();
+        block(&file)
+    }
+
+    fn sync_as_per_mode(&self, file: &File, file_name: &str) -> Result<(), Error> {
+        match self.sync_mode {
+            SyncMode::Always => self.sync_data(file),
+            SyncMode::Never => Ok(()),
+            SyncMode::Periodic(writes_between_syncs) => {
+                let mut unsynced_writes_by_file = self.unsynced_writes_by_file.lock().unwrap();
+                let unsynced_writes = unsynced_writes_by_file
+                    .entry(file_name.to_string())
+                    .or_insert(0);
+                *unsynced_writes += 1;
+                if *unsynced_writes >= writes_between_syncs {
+                    *unsynced_writes = 0;
+                    return self.sync_data(file);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn sync_data(&self, file: &File) -> Result<(), Error> {
+        file.sync_data()?;
+        self.io_counters.record_sync();
+        Ok(())
+    }
+
+    fn read_whole_block(&self, block_id: &BlockId) -> Result<Vec<u8>, Error> {
+        let mut read_buffer = vec![0; self.block_size_for(block_id.file_name())];
+        self.read_into(block_id, &mut read_buffer)?;
+        Ok(read_buffer)
+    }
+
+    fn read_block(
+        &self,
+        file: &File,
+        block_id: &BlockId,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error> {
+        let bytes_read = self.read_block_into(file, block_id, buffer)?;
+        self.io_counters.record_read(bytes_read);
+        Ok(bytes_read)
+    }
+
+    fn read_block_into(
+        &self,
+        file: &File,
+        block_id: &BlockId,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error> {
+        #[cfg(feature = "mmap")]
+        if self.use_mmap {
+            return self.read_mapped_block(file, block_id, buffer);
+        }
+        Ok(Self::read_available_at(
+            file,
+            self.file_offset_of(block_id),
+            buffer,
+        )?)
+    }
+
+    #[cfg(feature = "mmap")]
+    fn read_mapped_block(
+        &self,
+        file: &File,
+        block_id: &BlockId,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error> {
+        let mapping = self.mapping_for(file, block_id.file_name())?;
+        let starting_offset = FileHeader::SIZE
+            + block_id.starting_offset(self.block_size_for(block_id.file_name())) as usize;
+        let end_offset = mapping.len().min(starting_offset + buffer.len());
+        let available = end_offset.saturating_sub(starting_offset);
+
+        buffer[..available].copy_from_slice(&mapping[starting_offset..starting_offset + available]);
+        Ok(available)
+    }
+
+    #[cfg(feature = "mmap")]
+    fn mapping_for(&self, file: &File, file_name: &str) -> Result<Arc<memmap2::Mmap>, Error> {
+        let mut mappings = self.mappings.lock().unwrap();
+        if let Some(mapping) = mappings.get(file_name) {
+            return Ok(mapping.clone());
+        }
+        // SAFETY: the file is only modified through this FileManager, which drops the mapping
+        // on every write before the file can change underneath it.
+        let mapping = Arc::new(unsafe { memmap2::Mmap::map(file)? });
+        mappings.insert(file_name.to_string(), mapping.clone());
+        Ok(mapping)
+    }
+
+    fn invalidate_mapping(&self, _file_name: &str) {
+        #[cfg(feature = "mmap")]
+        self.mappings.lock().unwrap().remove(_file_name);
+    }
+
+    fn read_available(file: &mut File, buffer: &mut [u8]) -> Result<usize, io::Error> {
+        let mut number_of_bytes_read = 0;
+        while number_of_bytes_read < buffer.len() {
+            match file.read(&mut buffer[number_of_bytes_read..]) {
+                Ok(0) => break,
+                Ok(bytes_read) => number_of_bytes_read += bytes_read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(number_of_bytes_read)
+    }
+
+    fn read_available_at(file: &File, offset: u64, buffer: &mut [u8]) -> Result<usize, io::Error> {
+        #[cfg(unix)]
+        use std::os::unix::fs::FileExt;
+        #[cfg(windows)]
+        use std::os::windows::fs::FileExt;
+
+        let mut number_of_bytes_read = 0;
+        while number_of_bytes_read < buffer.len() {
+            let position = offset + number_of_bytes_read as u64;
+            #[cfg(unix)]
+            let result = file.read_at(&mut buffer[number_of_bytes_read..], position);
+            #[cfg(windows)]
+            let result = file.seek_read(&mut buffer[number_of_bytes_read..], position);
+            match result {
+                Ok(0) => break,
+                Ok(bytes_read) => number_of_bytes_read += bytes_read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(number_of_bytes_read)
+    }
+
+    fn write_scratch_file(&self, file_name: &str, data: &[u8]) -> Result<PathBuf, Error> {
+        let scratch_path = self
+            .directory
+            .as_ref()
+            .join(Path::new(&format!("{}.scratch", file_name)));
+        let mut file = File::create(&scratch_path)?;
+        file.write_all(&FileHeader::new(self.block_size_for(file_name)).encode())?;
+        file.write_all(data)?;
+        self.io_counters.record_write(FileHeader::SIZE + data.len());
+        self.sync_data(&file)?;
+        Ok(scratch_path)
+    }
+
+    fn write_or_validate_header(&self, file: &mut File, file_name: &str) -> Result<(), Error> {
+        let block_size = self.block_size_for(file_name);
+        if file.metadata()?.len() == 0 {
+            file.write_all(&FileHeader::new(block_size).encode())?;
+            self.io_counters.record_write(FileHeader::SIZE);
+            return Ok(());
+        }
+        let mut header = [0; FileHeader::SIZE];
+        let bytes_read = Self::read_available(file, &mut header)?;
+        self.io_counters.record_read(bytes_read);
+        FileHeader::decode_from(&header[..bytes_read])
+            .map_err(|_| {
+                Error::Corrupt(format!(
+                    "{} does not start with a valid file header",
+                    file_name
+                ))
+            })?
+            .validate(file_name, block_size)
+    }
+
+    fn forget_file(&self, file_name: &str) {
+        let path = self.directory.as_ref().join(Path::new(&file_name));
+        if let Some(path) = path.to_str() {
+            self.open_files.write().unwrap().remove(path);
+        }
+        self.number_of_blocks_by_file
+            .lock()
+            .unwrap()
+            .remove(file_name);
+        self.unsynced_writes_by_file
+            .lock()
+            .unwrap()
+            .remove(file_name);
+        self.invalidate_mapping(file_name);
+    }
+
+    fn get_or_create(&self, file_name: &str) -> Result<Arc<RwLock<File>>, Error> {
         let path = self.directory.as_ref().join(Path::new(&file_name));
         let path = path.to_str().unwrap();
 
-        let mut open_files = self.open_files.borrow_mut();
+        if let Some(file) = self.open_files.read().unwrap().get(path) {
+            return Ok(file.clone());
+        }
+        let mut open_files = self.open_files.write().unwrap();
         if !open_files.contains_key(path) {
-            let file = File::options()
+            let mut file = File::options()
                 .read(true)
                 .write(true)
                 .create(true)
+                .truncate(false)
                 .open(path)?;
+            self.write_or_validate_header(&mut file, file_name)?;
 
-            open_files.insert(path.to_string(), file);
+            open_files.insert(path.to_string(), Arc::new(RwLock::new(file)));
         }
-        Ok(RefMut::map(open_files, |files| {
-            files.get_mut(path).unwrap()
-        }))
+        Ok(open_files[path].clone())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::buffer::page::BufferPage;
+    use crate::error::Error;
     use crate::file::block_id::BlockId;
-    use crate::file::file_manager::FileManager;
+    use crate::file::file_header::FileHeader;
+    use crate::file::file_manager::{FileManager, FileManagerBuilder, SyncMode};
+    use crate::file::io_stats::IoStats;
     use crate::page::Page;
+    use std::io;
     use tempfile::NamedTempFile;
 
     const BLOCK_SIZE: usize = 4096;
@@ -104,8 +702,12 @@ mod tests {
     }
 
     impl Page for TestPage {
-        fn decode_from(buffer: Vec<u8>) -> Self {
-            TestPage { buffer }
+        fn decode_from(buffer: Vec<u8>) -> Result<Self, Error> {
+            Ok(TestPage { buffer })
+        }
+
+        fn encode(&mut self) -> &[u8] {
+            &self.buffer
         }
     }
 
@@ -153,6 +755,22 @@ mod tests {
         assert_eq!(&page.buffer[..write_buffer.len()], write_buffer);
     }
 
+    #[test]
+    fn attempt_to_read_a_block_beyond_the_end_of_file() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(file_name).unwrap();
+
+        let result = file_manager.read::<TestPage>(&BlockId::new(file_name, 1));
+        assert!(matches!(
+            result,
+            Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+    }
+
     #[test]
     fn number_of_blocks_zero() {
         let file = NamedTempFile::new().expect("Failed to create temp file");
@@ -181,6 +799,30 @@ mod tests {
         assert_eq!(1, number_of_blocks);
     }
 
+    #[test]
+    fn number_of_blocks_with_partially_written_block() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let write_buffer = b"PebbleDB is an LSM-based storage engine.";
+        let block_id = BlockId::new(file_name, 1);
+        let result = file_manager.write(&block_id, write_buffer);
+        assert!(result.is_ok());
+
+        assert_eq!(2, file_manager.number_of_blocks(file_name).unwrap());
+
+        let reopened_file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        assert_eq!(
+            2,
+            reopened_file_manager.number_of_blocks(file_name).unwrap()
+        );
+
+        let page = reopened_file_manager.read::<TestPage>(&block_id).unwrap();
+        assert_eq!(&page.buffer[..write_buffer.len()], write_buffer);
+    }
+
     #[test]
     fn append_empty_block() {
         let file = NamedTempFile::new().expect("Failed to create temp file");
@@ -217,4 +859,827 @@ mod tests {
         let new_block_id = file_manager.append_empty_block(file_name).unwrap();
         assert_eq!(1, new_block_id.block_number);
     }
+
+    #[test]
+    fn cached_number_of_blocks_matches_metadata_after_appending_blocks() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        (0..10).for_each(|_| {
+            file_manager.append_empty_block(file_name).unwrap();
+        });
+
+        assert_eq!(10, file_manager.number_of_blocks(file_name).unwrap());
+        assert_eq!(
+            file_manager
+                .with_file(file_name, |file| file_manager
+                    .number_of_blocks_from_metadata(file_name, file))
+                .unwrap(),
+            file_manager.number_of_blocks(file_name).unwrap()
+        );
+    }
+
+    #[test]
+    fn cached_number_of_blocks_is_extended_by_a_write_beyond_the_last_block() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        assert_eq!(0, file_manager.number_of_blocks(file_name).unwrap());
+
+        let result = file_manager.write(&BlockId::new(file_name, 3), &[0; BLOCK_SIZE]);
+        assert!(result.is_ok());
+
+        assert_eq!(4, file_manager.number_of_blocks(file_name).unwrap());
+        assert_eq!(
+            file_manager
+                .with_file(file_name, |file| file_manager
+                    .number_of_blocks_from_metadata(file_name, file))
+                .unwrap(),
+            file_manager.number_of_blocks(file_name).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_a_few_blocks_without_sync_and_sync_the_file_explicitly() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager =
+            FileManager::new_with_sync_mode(directory_path, BLOCK_SIZE, SyncMode::Never).unwrap();
+        (0..10).for_each(|block_number| {
+            let content = format!("Block {}", block_number);
+            let result =
+                file_manager.write(&BlockId::new(file_name, block_number), content.as_bytes());
+            assert!(result.is_ok());
+        });
+        assert!(file_manager.sync_file(file_name).is_ok());
+
+        (0..10).for_each(|block_number| {
+            let content = format!("Block {}", block_number);
+            let page = file_manager
+                .read::<TestPage>(&BlockId::new(file_name, block_number))
+                .unwrap();
+            assert_eq!(&page.buffer[..content.len()], content.as_bytes());
+        });
+    }
+
+    #[test]
+    fn write_a_few_blocks_with_periodic_sync() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager =
+            FileManager::new_with_sync_mode(directory_path, BLOCK_SIZE, SyncMode::Periodic(4))
+                .unwrap();
+        (0..6).for_each(|block_number| {
+            let result = file_manager.write(&BlockId::new(file_name, block_number), b"RocksDB");
+            assert!(result.is_ok());
+        });
+
+        assert_eq!(
+            Some(&2),
+            file_manager
+                .unsynced_writes_by_file
+                .lock()
+                .unwrap()
+                .get(file_name)
+        );
+    }
+
+    #[test]
+    fn read_a_few_blocks_into_a_reused_buffer() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let contents: [&[u8]; 3] = [
+            b"RocksDB is an LSM-based storage engine",
+            b"PebbleDB is an LSM-based storage engine",
+            b"BoltDB is a B+Tree storage engine",
+        ];
+        contents
+            .iter()
+            .enumerate()
+            .for_each(|(block_number, content)| {
+                let mut block = vec![0; BLOCK_SIZE];
+                block[..content.len()].copy_from_slice(content);
+                let result = file_manager.write(&BlockId::new(file_name, block_number), &block);
+                assert!(result.is_ok());
+            });
+
+        let mut buffer = vec![0; BLOCK_SIZE];
+        contents
+            .iter()
+            .enumerate()
+            .for_each(|(block_number, content)| {
+                let number_of_bytes_read = file_manager
+                    .read_into(&BlockId::new(file_name, block_number), &mut buffer)
+                    .unwrap();
+                assert_eq!(BLOCK_SIZE, number_of_bytes_read);
+                assert_eq!(&buffer[..content.len()], *content);
+            });
+    }
+
+    #[test]
+    fn write_blocks_from_a_few_threads_and_read_them_back() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        std::thread::scope(|scope| {
+            (0..8).for_each(|block_number| {
+                let file_manager = &file_manager;
+                scope.spawn(move || {
+                    let content = format!("Block written by thread {}", block_number);
+                    let result = file_manager
+                        .write(&BlockId::new(file_name, block_number), content.as_bytes());
+                    assert!(result.is_ok());
+                });
+            });
+        });
+
+        assert_eq!(8, file_manager.number_of_blocks(file_name).unwrap());
+        (0..8).for_each(|block_number| {
+            let content = format!("Block written by thread {}", block_number);
+            let page = file_manager
+                .read::<TestPage>(&BlockId::new(file_name, block_number))
+                .unwrap();
+            assert_eq!(&page.buffer[..content.len()], content.as_bytes());
+        });
+    }
+
+    #[test]
+    fn write_a_page_and_read_it_back() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(250).unwrap();
+        page.add_string("RocksDB").unwrap();
+
+        let block_id = BlockId::new(file_name, 0);
+        file_manager.write_page(&block_id, &mut page).unwrap();
+
+        let read_page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(250), read_page.get_u16(0).unwrap());
+        assert_eq!(Some("RocksDB"), read_page.get_string(1).unwrap());
+    }
+
+    #[test]
+    fn write_an_empty_page_and_read_it_back() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut page = BufferPage::new(BLOCK_SIZE);
+
+        let block_id = BlockId::new(file_name, 0);
+        file_manager.write_page(&block_id, &mut page).unwrap();
+
+        let read_page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert!(read_page.is_empty());
+    }
+
+    #[test]
+    fn read_a_corrupt_page_with_verification() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("RocksDB").unwrap();
+        page.add_u16(250).unwrap();
+
+        let block_id = BlockId::new(file_name, 0);
+        file_manager.write_page(&block_id, &mut page).unwrap();
+        assert!(file_manager.read_verified::<BufferPage>(&block_id).is_ok());
+
+        let mut block = vec![0; BLOCK_SIZE];
+        file_manager.read_into(&block_id, &mut block).unwrap();
+        block[..2].copy_from_slice(&[0xFF, 0xFF]);
+        file_manager.write(&block_id, &block).unwrap();
+
+        assert!(file_manager.read::<BufferPage>(&block_id).is_ok());
+        assert!(matches!(
+            file_manager.read_verified::<BufferPage>(&block_id),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn read_different_blocks_from_a_few_threads() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        (0..8).for_each(|block_number| {
+            let content = format!("Block {}", block_number);
+            file_manager
+                .write(&BlockId::new(file_name, block_number), content.as_bytes())
+                .unwrap();
+        });
+
+        std::thread::scope(|scope| {
+            (0..8).for_each(|block_number| {
+                let file_manager = &file_manager;
+                scope.spawn(move || {
+                    let content = format!("Block {}", block_number);
+                    (0..50).for_each(|_| {
+                        let page = file_manager
+                            .read::<TestPage>(&BlockId::new(file_name, block_number))
+                            .unwrap();
+                        assert_eq!(&page.buffer[..content.len()], content.as_bytes());
+                    });
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn read_a_block_while_another_read_holds_the_file() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager
+            .write(&BlockId::new(file_name, 0), b"RocksDB")
+            .unwrap();
+
+        let page = file_manager
+            .with_file_for_read(file_name, |_| {
+                file_manager.read::<TestPage>(&BlockId::new(file_name, 0))
+            })
+            .unwrap();
+        assert_eq!(b"RocksDB", &page.buffer[..7]);
+    }
+
+    #[test]
+    fn create_a_nested_directory() {
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+        let nested_directory = directory.path().join("picodb").join("data").join("blocks");
+
+        let file_manager = FileManager::new(&nested_directory, BLOCK_SIZE);
+        assert!(file_manager.is_ok());
+        assert!(nested_directory.is_dir());
+    }
+
+    #[test]
+    fn open_an_existing_directory() {
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+        FileManager::new(directory.path(), BLOCK_SIZE).unwrap();
+
+        assert!(FileManager::new(directory.path(), BLOCK_SIZE).is_ok());
+    }
+
+    #[test]
+    fn attempt_to_create_a_file_manager_over_a_regular_file() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+
+        let result = FileManager::new(file.path(), BLOCK_SIZE);
+        let Err(Error::Io(error)) = result else {
+            panic!("expected an io error for a path that is a regular file");
+        };
+        assert_eq!(io::ErrorKind::NotADirectory, error.kind());
+        assert!(error.to_string().contains("is not a directory"));
+    }
+
+    #[test]
+    fn append_a_block_with_data() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(file_name).unwrap();
+
+        let block_id = file_manager.append_block(file_name, b"RocksDB").unwrap();
+        assert_eq!(BlockId::new(file_name, 1), block_id);
+        assert_eq!(2, file_manager.number_of_blocks(file_name).unwrap());
+
+        let page = file_manager.read::<TestPage>(&block_id).unwrap();
+        assert_eq!(b"RocksDB", &page.buffer[..7]);
+        assert!(page.buffer[7..].iter().all(|byte| *byte == 0));
+        assert_eq!(BLOCK_SIZE, page.buffer.len());
+    }
+
+    #[test]
+    fn attempt_to_append_a_block_with_data_larger_than_the_block() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+
+        assert!(file_manager
+            .append_block(file_name, &vec![1; BLOCK_SIZE + 1])
+            .is_err());
+        assert_eq!(0, file_manager.number_of_blocks(file_name).unwrap());
+    }
+
+    #[test]
+    fn file_names_in_the_directory() {
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block("btree.db").unwrap();
+        file_manager.append_empty_block("wal.log").unwrap();
+
+        let mut file_names = file_manager.file_names().unwrap();
+        file_names.sort();
+        assert_eq!(
+            vec!["btree.db".to_string(), "wal.log".to_string()],
+            file_names
+        );
+    }
+
+    #[test]
+    fn delete_a_file() {
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block("btree.db").unwrap();
+        file_manager.append_empty_block("btree.db").unwrap();
+
+        assert!(file_manager.delete_file("btree.db").is_ok());
+        assert!(file_manager.file_names().unwrap().is_empty());
+        assert_eq!(0, file_manager.number_of_blocks("btree.db").unwrap());
+    }
+
+    #[test]
+    fn write_vectored_matches_a_single_write() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let mut content = vec![0; BLOCK_SIZE];
+        content[..7].copy_from_slice(b"RocksDB");
+        content[BLOCK_SIZE - 6..].copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager
+            .write(&BlockId::new(file_name, 0), &content)
+            .unwrap();
+        file_manager
+            .write_vectored(
+                &BlockId::new(file_name, 1),
+                &[
+                    &content[..7],
+                    &content[7..BLOCK_SIZE - 6],
+                    &content[BLOCK_SIZE - 6..],
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(2, file_manager.number_of_blocks(file_name).unwrap());
+        let on_disk = std::fs::read(file.path()).unwrap();
+        let blocks = &on_disk[FileHeader::SIZE..];
+        assert_eq!(&blocks[..BLOCK_SIZE], &blocks[BLOCK_SIZE..]);
+    }
+
+    #[test]
+    fn scan_all_the_blocks_with_a_single_buffer() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        for block_number in 0..5 {
+            file_manager
+                .write(
+                    &BlockId::new(file_name, block_number),
+                    &[block_number as u8 + 1; BLOCK_SIZE],
+                )
+                .unwrap();
+        }
+
+        let mut buffer = vec![0; BLOCK_SIZE];
+        let buffer_address = buffer.as_ptr();
+        let mut visited = Vec::new();
+
+        let number_of_blocks = file_manager
+            .scan(file_name, &mut buffer, |block_id, block| {
+                assert_eq!(buffer_address, block.as_ptr());
+                assert_eq!(BLOCK_SIZE, block.len());
+                visited.push((block_id.block_number, block[0]));
+            })
+            .unwrap();
+
+        assert_eq!(5, number_of_blocks);
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)], visited);
+    }
+
+    #[test]
+    fn iterate_over_all_the_blocks_of_a_file() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        for (block_number, content) in [b"RocksDB", b"BoltDB ", b"Pebble "].iter().enumerate() {
+            let mut block = vec![0; BLOCK_SIZE];
+            block[..content.len()].copy_from_slice(*content);
+            file_manager
+                .write(&BlockId::new(file_name, block_number), &block)
+                .unwrap();
+        }
+
+        let blocks = file_manager
+            .blocks(file_name)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(3, blocks.len());
+        assert_eq!(b"RocksDB", &blocks[0][..7]);
+        assert_eq!(b"BoltDB ", &blocks[1][..7]);
+        assert_eq!(b"Pebble ", &blocks[2][..7]);
+        assert!(blocks.iter().all(|block| block.len() == BLOCK_SIZE));
+    }
+
+    #[test]
+    fn use_a_different_block_size_for_a_file() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.use_block_size_for(file_name, 64).unwrap();
+
+        file_manager.append_block(file_name, b"RocksDB").unwrap();
+        let block_id = file_manager.append_block(file_name, b"PebbleDB").unwrap();
+        assert_eq!(1, block_id.block_number);
+        assert_eq!(64, file_manager.block_size_for(file_name));
+        assert_eq!(BLOCK_SIZE, file_manager.block_size_for("other"));
+
+        let page = file_manager.read::<TestPage>(&block_id).unwrap();
+        assert_eq!(64, page.buffer.len());
+        assert_eq!(b"PebbleDB", &page.buffer[..8]);
+
+        let reloaded_file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        reloaded_file_manager
+            .use_block_size_for(file_name, 64)
+            .unwrap();
+        assert_eq!(
+            2,
+            reloaded_file_manager.number_of_blocks(file_name).unwrap()
+        );
+    }
+
+    #[test]
+    fn attempt_to_create_a_file_manager_with_a_zero_block_size() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+
+        assert!(matches!(
+            FileManager::new(directory_path, 0),
+            Err(Error::InvalidBlockSize { block_size: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn attempt_to_create_a_file_manager_with_a_tiny_block_size() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+
+        assert!(matches!(
+            FileManager::new(directory_path, 8),
+            Err(Error::InvalidBlockSize { block_size: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn preallocate_blocks() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(file_name).unwrap();
+        file_manager.preallocate(file_name, 100).unwrap();
+
+        assert_eq!(100, file_manager.number_of_blocks(file_name).unwrap());
+        assert_eq!(
+            (FileHeader::SIZE + 100 * BLOCK_SIZE) as u64,
+            std::fs::metadata(file.path()).unwrap().len()
+        );
+        let page = file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 99))
+            .unwrap();
+        assert_eq!(vec![0; BLOCK_SIZE], page.buffer);
+    }
+
+    #[test]
+    fn preallocate_fewer_blocks_than_the_file_has() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.preallocate(file_name, 10).unwrap();
+        file_manager.preallocate(file_name, 5).unwrap();
+
+        assert_eq!(10, file_manager.number_of_blocks(file_name).unwrap());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn read_blocks_through_the_memory_mapping() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        for block_number in 0..3 {
+            file_manager
+                .write(
+                    &BlockId::new(file_name, block_number),
+                    &[block_number as u8 + 1; BLOCK_SIZE],
+                )
+                .unwrap();
+        }
+        let on_disk = std::fs::read(file.path()).unwrap();
+        let on_disk = &on_disk[FileHeader::SIZE..];
+
+        for block_number in 0..3 {
+            let page = file_manager
+                .read::<TestPage>(&BlockId::new(file_name, block_number))
+                .unwrap();
+            assert_eq!(
+                &on_disk[block_number * BLOCK_SIZE..(block_number + 1) * BLOCK_SIZE],
+                &page.buffer[..]
+            );
+        }
+
+        file_manager
+            .write(&BlockId::new(file_name, 3), &[4; BLOCK_SIZE])
+            .unwrap();
+        let page = file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 3))
+            .unwrap();
+        assert_eq!(vec![4; BLOCK_SIZE], page.buffer);
+    }
+
+    #[test]
+    fn count_io_operations() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        assert_eq!(IoStats::default(), file_manager.io_stats());
+
+        let block_id = file_manager.append_empty_block(file_name).unwrap();
+        file_manager
+            .write(&block_id, b"RocksDB is an LSM-based storage engine")
+            .unwrap();
+        file_manager.read::<TestPage>(&block_id).unwrap();
+        file_manager.sync_file(file_name).unwrap();
+
+        assert_eq!(
+            IoStats {
+                reads: 1,
+                writes: 3,
+                bytes_read: BLOCK_SIZE,
+                bytes_written: FileHeader::SIZE + BLOCK_SIZE + 38,
+                syncs: 3,
+            },
+            file_manager.io_stats()
+        );
+    }
+
+    #[test]
+    fn count_no_syncs_when_never_syncing() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager =
+            FileManager::new_with_sync_mode(directory_path, BLOCK_SIZE, SyncMode::Never).unwrap();
+        file_manager.append_empty_block(file_name).unwrap();
+        file_manager
+            .write_vectored(&BlockId::new(file_name, 0), &[b"Rocks", b"DB"])
+            .unwrap();
+
+        let io_stats = file_manager.io_stats();
+        assert_eq!(3, io_stats.writes);
+        assert_eq!(FileHeader::SIZE + BLOCK_SIZE + 7, io_stats.bytes_written);
+        assert_eq!(0, io_stats.syncs);
+    }
+
+    #[test]
+    fn replace_the_content_of_a_file_atomically() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager
+            .write(&BlockId::new(file_name, 0), &[b'o'; BLOCK_SIZE])
+            .unwrap();
+        file_manager
+            .write(&BlockId::new(file_name, 1), &[b'o'; BLOCK_SIZE])
+            .unwrap();
+
+        file_manager
+            .write_atomic(file_name, &[b'n'; BLOCK_SIZE])
+            .unwrap();
+
+        assert_eq!(1, file_manager.number_of_blocks(file_name).unwrap());
+        let page = file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 0))
+            .unwrap();
+        assert_eq!(vec![b'n'; BLOCK_SIZE], page.buffer);
+        assert!(!file_manager
+            .file_names()
+            .unwrap()
+            .contains(&format!("{}.scratch", file_name)));
+    }
+
+    #[test]
+    fn keep_the_old_content_when_an_atomic_write_crashes_midway() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager
+            .write_atomic(file_name, &[b'o'; BLOCK_SIZE])
+            .unwrap();
+
+        let new_content = [b'n'; BLOCK_SIZE];
+        file_manager
+            .write_scratch_file(file_name, &new_content[..BLOCK_SIZE / 2])
+            .unwrap();
+        drop(file_manager);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let page = file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 0))
+            .unwrap();
+        assert_eq!(vec![b'o'; BLOCK_SIZE], page.buffer);
+
+        file_manager.write_atomic(file_name, &new_content).unwrap();
+        let page = file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 0))
+            .unwrap();
+        assert_eq!(new_content.to_vec(), page.buffer);
+    }
+
+    #[test]
+    fn attempt_to_reopen_a_file_with_a_different_block_size() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(file_name).unwrap();
+        drop(file_manager);
+
+        let file_manager = FileManager::new(directory_path, 512).unwrap();
+        let error = file_manager.number_of_blocks(file_name).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BlockSizeMismatch {
+                block_size: 512,
+                expected: BLOCK_SIZE,
+                ..
+            }
+        ));
+        assert_eq!(
+            format!(
+                "File {} was created with a block size of 4096 but is opened with 512",
+                file_name
+            ),
+            error.to_string()
+        );
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        assert_eq!(1, file_manager.number_of_blocks(file_name).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_open_a_file_without_a_file_header() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+        std::fs::write(file.path(), b"RocksDB is an LSM-based storage engine").unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        assert!(matches!(
+            file_manager.number_of_blocks(file_name),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn build_a_file_manager_with_non_default_options() {
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+        let directory_path = directory.path().join("lsm").join("sstables");
+
+        let file_manager = FileManagerBuilder::new(&directory_path)
+            .block_size(512)
+            .sync_mode(SyncMode::Never)
+            .build()
+            .unwrap();
+        assert_eq!(512, file_manager.block_size);
+        assert!(directory_path.is_dir());
+
+        let block_id = file_manager.append_block("btree.db", b"RocksDB").unwrap();
+        let page = file_manager.read::<TestPage>(&block_id).unwrap();
+        assert_eq!(b"RocksDB", &page.buffer[..7]);
+        assert_eq!(0, file_manager.io_stats().syncs);
+    }
+
+    #[test]
+    fn attempt_to_build_a_file_manager_over_a_missing_directory_without_creating_it() {
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+        let directory_path = directory.path().join("missing");
+
+        let result = FileManagerBuilder::new(&directory_path)
+            .create_dirs(false)
+            .build();
+        assert!(matches!(result, Err(Error::Io(ref err)) if err.kind() == io::ErrorKind::NotFound));
+        assert!(!directory_path.exists());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn read_blocks_without_the_memory_mapping() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManagerBuilder::new(directory_path)
+            .block_size(BLOCK_SIZE)
+            .mmap(false)
+            .build()
+            .unwrap();
+        file_manager
+            .write(&BlockId::new(file_name, 0), &[7; BLOCK_SIZE])
+            .unwrap();
+
+        let page = file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 0))
+            .unwrap();
+        assert_eq!(vec![7; BLOCK_SIZE], page.buffer);
+        assert!(file_manager.mappings.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncate_a_file_to_fewer_blocks() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        for block_number in 0..5 {
+            file_manager
+                .append_block(file_name, &[block_number as u8 + 1; BLOCK_SIZE])
+                .unwrap();
+        }
+
+        file_manager.truncate_to_blocks(file_name, 2).unwrap();
+        assert_eq!(2, file_manager.number_of_blocks(file_name).unwrap());
+        for block_number in 0..2 {
+            let page = file_manager
+                .read::<TestPage>(&BlockId::new(file_name, block_number))
+                .unwrap();
+            assert_eq!(vec![block_number as u8 + 1; BLOCK_SIZE], page.buffer);
+        }
+        assert!(file_manager
+            .read::<TestPage>(&BlockId::new(file_name, 2))
+            .is_err());
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        assert_eq!(2, file_manager.number_of_blocks(file_name).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_grow_a_file_by_truncating_it() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        file_manager.append_empty_block(file_name).unwrap();
+
+        assert!(matches!(
+            file_manager.truncate_to_blocks(file_name, 3),
+            Err(Error::Io(ref err)) if err.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert_eq!(1, file_manager.number_of_blocks(file_name).unwrap());
+        assert!(file_manager.truncate_to_blocks(file_name, 1).is_ok());
+    }
 }
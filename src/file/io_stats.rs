@@ -0,0 +1,48 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IoStats {
+    pub(crate) reads: usize,
+    pub(crate) writes: usize,
+    pub(crate) bytes_read: usize,
+    pub(crate) bytes_written: usize,
+    pub(crate) syncs: usize,
+}
+
+// Counters are only observed through snapshots, so relaxed ordering is enough.
+#[derive(Default)]
+pub(crate) struct IoCounters {
+    reads: AtomicUsize,
+    writes: AtomicUsize,
+    bytes_read: AtomicUsize,
+    bytes_written: AtomicUsize,
+    syncs: AtomicUsize,
+}
+
+impl IoCounters {
+    pub(crate) fn record_read(&self, number_of_bytes: usize) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read
+            .fetch_add(number_of_bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_write(&self, number_of_bytes: usize) {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written
+            .fetch_add(number_of_bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_sync(&self) {
+        self.syncs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> IoStats {
+        IoStats {
+            reads: self.reads.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            syncs: self.syncs.load(Ordering::Relaxed),
+        }
+    }
+}
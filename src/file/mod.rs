@@ -1,3 +1,6 @@
 pub(crate) mod block_id;
+pub(crate) mod block_size;
+pub(crate) mod file_header;
 pub(crate) mod file_manager;
+pub(crate) mod io_stats;
 pub(crate) mod starting_offsets;
@@ -1,7 +1,9 @@
+use crate::error::Error;
 use byteorder::ByteOrder;
 
 const SIZE_OF_OFFSET: usize = size_of::<u32>();
 
+#[derive(Clone)]
 pub(crate) struct StartingOffsets {
     offsets: Vec<u32>,
 }
@@ -21,14 +23,87 @@ impl StartingOffsets {
         starting_offsets
     }
 
-    pub(crate) fn add_offset(&mut self, offset: u32) {
-        self.offsets.push(offset);
+    /// Ensures the offsets are strictly increasing and lie before `end_of_data`, which holds for
+    /// pages whose fields always occupy at least one byte.
+    pub(crate) fn validate(&self, end_of_data: usize) -> Result<(), Error> {
+        if let Some(index) = self.offsets.windows(2).position(|pair| pair[0] >= pair[1]) {
+            return Err(Error::Corrupt(format!(
+                "offset {} at index {} is not less than the next offset {}",
+                self.offsets[index],
+                index,
+                self.offsets[index + 1]
+            )));
+        }
+        match self.offsets.last() {
+            Some(&last_offset) if last_offset as usize >= end_of_data => {
+                Err(Error::Corrupt(format!(
+                    "offset {} lies beyond the data ending at {}",
+                    last_offset, end_of_data
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn add_offset(&mut self, offset: usize) -> Result<(), Error> {
+        self.offsets.push(Self::encodable(offset)?);
+        Ok(())
+    }
+
+    pub(crate) fn insert_offset(&mut self, index: usize, offset: usize) -> Result<(), Error> {
+        self.offsets.insert(index, Self::encodable(offset)?);
+        Ok(())
+    }
+
+    pub(crate) fn remove_offset(&mut self, index: usize) -> Option<u32> {
+        if index >= self.offsets.len() {
+            return None;
+        }
+        Some(self.offsets.remove(index))
+    }
+
+    /// Shifts every offset from `index` onwards, leaving all of them unchanged if any one of
+    /// them would fall below zero or beyond u32.
+    pub(crate) fn shift_from(&mut self, index: usize, delta: i64) -> Result<(), Error> {
+        let shifted = self
+            .offsets
+            .iter()
+            .skip(index)
+            .map(|&offset| match usize::try_from(offset as i64 + delta) {
+                Ok(shifted) => Self::encodable(shifted),
+                Err(_) => Err(Error::Corrupt(format!(
+                    "offset {} shifted by {} is negative",
+                    offset, delta
+                ))),
+            })
+            .collect::<Result<Vec<u32>, Error>>()?;
+        self.offsets.truncate(index);
+        self.offsets.extend(shifted);
+        Ok(())
     }
 
     pub(crate) fn offset_at(&self, index: usize) -> Option<&u32> {
         self.offsets.get(index)
     }
 
+    pub(crate) fn offset_value_at(&self, index: usize) -> Option<u32> {
+        self.offsets.get(index).copied()
+    }
+
+    /// Requires the offsets to be sorted in ascending order, which holds for append-only pages.
+    pub(crate) fn index_of_offset(&self, offset: u32) -> Option<usize> {
+        self.offsets.binary_search(&offset).ok()
+    }
+
+    /// Returns the index of the largest offset less than or equal to the given offset.
+    /// Requires the offsets to be sorted in ascending order, which holds for append-only pages.
+    pub(crate) fn search_le(&self, offset: u32) -> Option<usize> {
+        match self.offsets.binary_search(&offset) {
+            Ok(index) => Some(index),
+            Err(insertion_index) => insertion_index.checked_sub(1),
+        }
+    }
+
     pub(crate) fn last_offset(&self) -> Option<&u32> {
         self.offsets.last()
     }
@@ -56,16 +131,24 @@ impl StartingOffsets {
     pub(crate) fn length(&self) -> usize {
         self.offsets.len()
     }
+
+    fn encodable(offset: usize) -> Result<u32, Error> {
+        u32::try_from(offset).map_err(|_| Error::OffsetTooLarge {
+            offset,
+            maximum: u32::MAX as usize,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::file::starting_offsets::StartingOffsets;
 
     #[test]
     fn encode_decode_starting_a_single_starting_offset() {
         let mut starting_offsets = StartingOffsets::new();
-        starting_offsets.add_offset(20);
+        starting_offsets.add_offset(20).unwrap();
 
         let encoded = starting_offsets.encode();
         let decoded = StartingOffsets::decode_from(&encoded);
@@ -76,9 +159,9 @@ mod tests {
     #[test]
     fn encode_decode_starting_starting_offsets_with_a_few_offsets() {
         let mut starting_offsets = StartingOffsets::new();
-        starting_offsets.add_offset(20);
-        starting_offsets.add_offset(400);
-        starting_offsets.add_offset(520);
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(520).unwrap();
 
         let encoded = starting_offsets.encode();
         let decoded = StartingOffsets::decode_from(&encoded);
@@ -87,4 +170,199 @@ mod tests {
         assert_eq!(Some(&400), decoded.offset_at(1));
         assert_eq!(Some(&520), decoded.offset_at(2));
     }
+
+    #[test]
+    fn validate_strictly_increasing_offsets() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+
+        let decoded = StartingOffsets::decode_from(&starting_offsets.encode());
+        assert!(decoded.validate(512).is_ok());
+        assert!(StartingOffsets::new().validate(0).is_ok());
+    }
+
+    #[test]
+    fn attempt_to_validate_offsets_with_a_descending_pair() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(20).unwrap();
+
+        let decoded = StartingOffsets::decode_from(&starting_offsets.encode());
+        assert!(matches!(decoded.validate(512), Err(Error::Corrupt(_))));
+    }
+
+    #[test]
+    fn attempt_to_validate_duplicate_offsets() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(20).unwrap();
+
+        let decoded = StartingOffsets::decode_from(&starting_offsets.encode());
+        assert!(matches!(decoded.validate(512), Err(Error::Corrupt(_))));
+    }
+
+    #[test]
+    fn attempt_to_validate_an_offset_beyond_the_data() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(512).unwrap();
+
+        let decoded = StartingOffsets::decode_from(&starting_offsets.encode());
+        assert!(matches!(decoded.validate(512), Err(Error::Corrupt(_))));
+    }
+
+    #[test]
+    fn offset_value_at_an_index() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+
+        assert_eq!(Some(20), starting_offsets.offset_value_at(0));
+        assert_eq!(Some(400), starting_offsets.offset_value_at(1));
+        assert_eq!(None, starting_offsets.offset_value_at(2));
+    }
+
+    #[test]
+    fn index_of_an_existing_offset() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(520).unwrap();
+
+        assert_eq!(Some(0), starting_offsets.index_of_offset(20));
+        assert_eq!(Some(1), starting_offsets.index_of_offset(400));
+        assert_eq!(Some(2), starting_offsets.index_of_offset(520));
+    }
+
+    #[test]
+    fn index_of_a_non_existing_offset() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+
+        assert_eq!(None, starting_offsets.index_of_offset(200));
+        assert_eq!(None, starting_offsets.index_of_offset(600));
+    }
+
+    #[test]
+    fn search_less_than_or_equal_with_an_exact_hit() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(520).unwrap();
+
+        assert_eq!(Some(1), starting_offsets.search_le(400));
+    }
+
+    #[test]
+    fn search_less_than_or_equal_between_offsets() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(520).unwrap();
+
+        assert_eq!(Some(0), starting_offsets.search_le(399));
+        assert_eq!(Some(1), starting_offsets.search_le(519));
+    }
+
+    #[test]
+    fn search_less_than_or_equal_out_of_range() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+
+        assert_eq!(None, starting_offsets.search_le(10));
+        assert_eq!(Some(1), starting_offsets.search_le(5000));
+        assert_eq!(None, StartingOffsets::new().search_le(10));
+    }
+
+    #[test]
+    fn remove_an_offset_in_the_middle() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(520).unwrap();
+
+        assert_eq!(Some(400), starting_offsets.remove_offset(1));
+        assert_eq!(2, starting_offsets.length());
+        assert_eq!(Some(&20), starting_offsets.offset_at(0));
+        assert_eq!(Some(&520), starting_offsets.offset_at(1));
+    }
+
+    #[test]
+    fn attempt_to_remove_a_non_existing_offset() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(20).unwrap();
+
+        assert_eq!(None, starting_offsets.remove_offset(1));
+        assert_eq!(1, starting_offsets.length());
+    }
+
+    #[test]
+    fn shift_a_suffix_of_offsets_by_a_negative_delta() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(400).unwrap();
+        starting_offsets.add_offset(520).unwrap();
+
+        starting_offsets.remove_offset(1);
+        starting_offsets.shift_from(1, -20).unwrap();
+
+        assert_eq!(Some(&0), starting_offsets.offset_at(0));
+        assert_eq!(Some(&380), starting_offsets.offset_at(1));
+        assert_eq!(Some(&500), starting_offsets.offset_at(2));
+    }
+
+    #[test]
+    fn shift_a_suffix_of_offsets_by_a_positive_delta() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(20).unwrap();
+
+        starting_offsets.shift_from(1, 30).unwrap();
+
+        assert_eq!(Some(&0), starting_offsets.offset_at(0));
+        assert_eq!(Some(&50), starting_offsets.offset_at(1));
+    }
+
+    #[test]
+    fn attempt_to_add_an_offset_beyond_u32() {
+        let mut starting_offsets = StartingOffsets::new();
+
+        assert!(matches!(
+            starting_offsets.add_offset(u32::MAX as usize + 1),
+            Err(Error::OffsetTooLarge { offset, .. }) if offset == u32::MAX as usize + 1
+        ));
+        assert!(matches!(
+            starting_offsets.insert_offset(0, u32::MAX as usize + 1),
+            Err(Error::OffsetTooLarge { .. })
+        ));
+        assert_eq!(0, starting_offsets.length());
+
+        assert!(starting_offsets.add_offset(u32::MAX as usize).is_ok());
+        assert_eq!(Some(&u32::MAX), starting_offsets.offset_at(0));
+    }
+
+    #[test]
+    fn attempt_to_shift_offsets_beyond_u32_or_below_zero() {
+        let mut starting_offsets = StartingOffsets::new();
+        starting_offsets.add_offset(0).unwrap();
+        starting_offsets.add_offset(20).unwrap();
+        starting_offsets.add_offset(u32::MAX as usize - 10).unwrap();
+
+        assert!(matches!(
+            starting_offsets.shift_from(1, 20),
+            Err(Error::OffsetTooLarge { offset, .. }) if offset == u32::MAX as usize + 10
+        ));
+        assert!(matches!(
+            starting_offsets.shift_from(0, -10),
+            Err(Error::Corrupt(_))
+        ));
+        assert_eq!(Some(&20), starting_offsets.offset_at(1));
+        assert_eq!(Some(&(u32::MAX - 10)), starting_offsets.offset_at(2));
+    }
 }
@@ -0,0 +1,91 @@
+const FNV_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+const FNV_PRIME: u64 = 0x100000001B3;
+const FNV_32_OFFSET_BASIS: u32 = 0x811C9DC5;
+const FNV_32_PRIME: u32 = 0x01000193;
+
+pub(crate) trait KeyHasher {
+    fn hash(&self, key: &[u8]) -> u64;
+}
+
+#[derive(Default)]
+pub(crate) struct Fnv1aHasher;
+
+// Hashes the seed ahead of the key, so hashers with different seeds behave as independent
+// hash functions over the same keys.
+pub(crate) struct SeededFnv1aHasher {
+    seed: u64,
+}
+
+impl KeyHasher for Fnv1aHasher {
+    fn hash(&self, key: &[u8]) -> u64 {
+        fnv1a(FNV_OFFSET_BASIS, key)
+    }
+}
+
+impl SeededFnv1aHasher {
+    pub(crate) fn new(seed: u64) -> Self {
+        SeededFnv1aHasher { seed }
+    }
+}
+
+impl KeyHasher for SeededFnv1aHasher {
+    fn hash(&self, key: &[u8]) -> u64 {
+        fnv1a(fnv1a(FNV_OFFSET_BASIS, &self.seed.to_le_bytes()), key)
+    }
+}
+
+/// The 32-bit variant, for formats that store 32-bit hashes.
+pub(crate) fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(FNV_32_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(FNV_32_PRIME)
+    })
+}
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::{fnv1a_32, Fnv1aHasher, KeyHasher, SeededFnv1aHasher};
+
+    #[test]
+    fn fnv1a_of_empty_bytes() {
+        assert_eq!(0xCBF29CE484222325, Fnv1aHasher.hash(b""));
+    }
+
+    #[test]
+    fn fnv1a_of_a_single_byte() {
+        assert_eq!(0xAF63DC4C8601EC8C, Fnv1aHasher.hash(b"a"));
+    }
+
+    #[test]
+    fn fnv1a_32_of_empty_bytes_and_a_single_byte() {
+        assert_eq!(0x811C9DC5, fnv1a_32(b""));
+        assert_eq!(0xE40C292C, fnv1a_32(b"a"));
+    }
+
+    #[test]
+    fn hash_the_same_key_deterministically() {
+        let hasher = SeededFnv1aHasher::new(7);
+        assert_eq!(hasher.hash(b"RocksDB"), hasher.hash(b"RocksDB"));
+        assert_eq!(
+            hasher.hash(b"RocksDB"),
+            SeededFnv1aHasher::new(7).hash(b"RocksDB")
+        );
+        assert_eq!(Fnv1aHasher.hash(b"RocksDB"), Fnv1aHasher.hash(b"RocksDB"));
+    }
+
+    #[test]
+    fn hash_the_same_key_differently_with_different_seeds() {
+        let hashes: Vec<u64> = (0..8)
+            .map(|seed| SeededFnv1aHasher::new(seed).hash(b"RocksDB"))
+            .collect();
+
+        (0..hashes.len()).for_each(|index| {
+            assert!(!hashes[index + 1..].contains(&hashes[index]));
+        });
+    }
+}
@@ -1,10 +1,21 @@
 use std::borrow::Cow;
 
+mod bloom;
 mod buffer;
+mod checksum;
 mod encodex;
+mod error;
 mod file;
+mod hash;
 mod log;
+mod memtable;
 mod page;
+mod record;
+mod recovery;
+mod sstable;
+mod transaction;
+
+pub use error::Error;
 
 pub(crate) fn assert_borrowed_type<T: ?Sized + ToOwned>(value: Cow<T>) -> &T {
     match value {
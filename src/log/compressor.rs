@@ -0,0 +1,147 @@
+use crate::error::Error;
+
+const UNCOMPRESSED: u8 = 0;
+const COMPRESSED: u8 = 1;
+
+pub(crate) trait Compressor: Send + Sync {
+    fn compress(&self, data: &[u8]) -> Vec<u8>;
+
+    fn decompress(&self, data: &[u8]) -> Vec<u8>;
+}
+
+pub(crate) struct NoOpCompressor;
+
+pub(crate) struct RunLengthCompressor;
+
+impl Compressor for NoOpCompressor {
+    fn compress(&self, data: &[u8]) -> Vec<u8> {
+        data.to_vec()
+    }
+
+    fn decompress(&self, data: &[u8]) -> Vec<u8> {
+        data.to_vec()
+    }
+}
+
+impl Compressor for RunLengthCompressor {
+    fn compress(&self, data: &[u8]) -> Vec<u8> {
+        let mut compressed = Vec::new();
+        for chunk in data.chunk_by(|left, right| left == right) {
+            for run in chunk.chunks(u8::MAX as usize) {
+                compressed.push(run.len() as u8);
+                compressed.push(run[0]);
+            }
+        }
+        compressed
+    }
+
+    fn decompress(&self, data: &[u8]) -> Vec<u8> {
+        let mut decompressed = Vec::new();
+        for pair in data.chunks_exact(2) {
+            decompressed.extend(std::iter::repeat_n(pair[1], pair[0] as usize));
+        }
+        decompressed
+    }
+}
+
+pub(crate) fn encode_record(compressor: &dyn Compressor, record: &[u8]) -> Vec<u8> {
+    let compressed = compressor.compress(record);
+    let (flag, payload) = if compressed.len() < record.len() {
+        (COMPRESSED, &compressed[..])
+    } else {
+        (UNCOMPRESSED, record)
+    };
+    let mut encoded = Vec::with_capacity(payload.len() + 1);
+    encoded.push(flag);
+    encoded.extend_from_slice(payload);
+    encoded
+}
+
+pub(crate) fn decode_record(compressor: &dyn Compressor, encoded: &[u8]) -> Result<Vec<u8>, Error> {
+    match encoded.split_first() {
+        Some((&COMPRESSED, payload)) => Ok(compressor.decompress(payload)),
+        Some((&UNCOMPRESSED, payload)) => Ok(payload.to_vec()),
+        Some((flag, _)) => Err(Error::Decode(format!(
+            "unknown compression flag {} in a log record",
+            flag
+        ))),
+        None => Err(Error::Decode(
+            "log record is missing its compression flag".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::log::compressor::{
+        decode_record, encode_record, Compressor, NoOpCompressor, RunLengthCompressor,
+    };
+
+    #[test]
+    fn compress_decompress_with_run_length_compressor() {
+        let data = b"aaaaaaaaaabbbbbbbbbbbbbbbbbbbbcd";
+        let compressed = RunLengthCompressor.compress(data);
+
+        assert_eq!(vec![10, b'a', 20, b'b', 1, b'c', 1, b'd'], compressed);
+        assert_eq!(data.to_vec(), RunLengthCompressor.decompress(&compressed));
+    }
+
+    #[test]
+    fn compress_decompress_a_run_longer_than_u8_with_run_length_compressor() {
+        let data = vec![b'a'; 600];
+        let compressed = RunLengthCompressor.compress(&data);
+
+        assert_eq!(6, compressed.len());
+        assert_eq!(data, RunLengthCompressor.decompress(&compressed));
+    }
+
+    #[test]
+    fn encode_decode_a_compressible_record() {
+        let record = vec![b'r'; 100];
+        let encoded = encode_record(&RunLengthCompressor, &record);
+
+        assert!(encoded.len() < record.len());
+        assert_eq!(
+            record,
+            decode_record(&RunLengthCompressor, &encoded).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_decode_an_incompressible_record() {
+        let record = b"RocksDB";
+        let encoded = encode_record(&RunLengthCompressor, record);
+
+        assert_eq!(record.len() + 1, encoded.len());
+        assert_eq!(
+            record.to_vec(),
+            decode_record(&RunLengthCompressor, &encoded).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_decode_a_record_with_no_op_compressor() {
+        let record = vec![b'r'; 100];
+        let encoded = encode_record(&NoOpCompressor, &record);
+
+        assert_eq!(record.len() + 1, encoded.len());
+        assert_eq!(record, decode_record(&NoOpCompressor, &encoded).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_decode_a_record_without_a_compression_flag() {
+        assert!(matches!(
+            decode_record(&RunLengthCompressor, &[]),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_a_record_with_an_unknown_compression_flag() {
+        assert!(matches!(
+            decode_record(&RunLengthCompressor, &[7, b'r']),
+            Err(Error::Decode(_))
+        ));
+    }
+}
@@ -1,35 +1,65 @@
+use crate::error::Error;
 use crate::file::block_id::BlockId;
 use crate::file::file_manager::FileManager;
-use crate::log::page::{BackwardRecordIterator, LogPage};
-use std::io;
+use crate::log::compressor::{decode_record, Compressor};
+use crate::log::page::{BackwardRecordIterator, ForwardRecordIterator, LogPage};
+use crate::log::sequenced_record::decode_sequenced_record;
 use std::path::Path;
-use std::rc::Rc;
+use std::sync::Arc;
 
+/// Yields the records from the latest to the oldest. A block that cannot be read or fails its
+/// checksum is yielded as an error, after which the iteration ends.
 pub(crate) struct BackwardLogIterator<'a, PathType: AsRef<Path>> {
     file_manager: &'a FileManager<PathType>,
     current_block_id: BlockId,
     record_iterator: BackwardRecordIterator,
+    compressor: Arc<dyn Compressor>,
+    previous_segment_file_names: Vec<String>,
+    failed: bool,
+}
+
+/// Yields the records from the oldest to the latest. A block that cannot be read or fails its
+/// checksum is yielded as an error, after which the iteration ends.
+pub(crate) struct ForwardLogIterator<'a, PathType: AsRef<Path>> {
+    file_manager: &'a FileManager<PathType>,
+    current_block_id: BlockId,
+    last_block_id: BlockId,
+    record_iterator: ForwardRecordIterator,
+    compressor: Arc<dyn Compressor>,
+    next_segment_file_names: Vec<String>,
+    from_log_sequence_number: usize,
+    failed: bool,
 }
 
 impl<PathType: AsRef<Path>> Iterator for BackwardLogIterator<'_, PathType> {
-    type Item = Vec<u8>;
+    type Item = Result<Vec<u8>, Error>;
 
     //TODO: avoid copy in the return type
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(record) = self.record_iterator.record() {
-            return Some(record.to_vec());
-        }
-        if self.current_block_id.block_number > 0 {
-            self.current_block_id = self.current_block_id.previous().unwrap();
-            let page = self
-                .file_manager
-                .read::<LogPage>(&self.current_block_id)
-                .unwrap();
-
-            self.record_iterator = BackwardRecordIterator::new(Rc::new(page));
-            return self.record_iterator.record().map(Vec::from);
+        loop {
+            if self.failed {
+                return None;
+            }
+            if let Some(record) = self.record_iterator.record() {
+                let decoded = decode_sequenced_record(record)
+                    .and_then(|(_, record)| decode_record(self.compressor.as_ref(), record));
+                self.failed = decoded.is_err();
+                return Some(decoded);
+            }
+            if self.record_iterator.is_corrupted() {
+                return None;
+            }
+            match self.previous_page() {
+                Ok(Some(page)) => {
+                    self.record_iterator = BackwardRecordIterator::new(Arc::new(page))
+                }
+                Ok(None) => return None,
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err));
+                }
+            }
         }
-        None
     }
 }
 
@@ -37,13 +67,132 @@ impl<'a, PathType: AsRef<Path>> BackwardLogIterator<'a, PathType> {
     pub(crate) fn new(
         file_manager: &'a FileManager<PathType>,
         current_block_id: BlockId,
-    ) -> Result<BackwardLogIterator<'a, PathType>, io::Error> {
+        compressor: Arc<dyn Compressor>,
+        previous_segment_file_names: Vec<String>,
+    ) -> Result<BackwardLogIterator<'a, PathType>, Error> {
         let page = file_manager.read::<LogPage>(&current_block_id)?;
 
         Ok(BackwardLogIterator {
             file_manager,
             current_block_id,
-            record_iterator: BackwardRecordIterator::new(Rc::new(page)),
+            record_iterator: BackwardRecordIterator::new(Arc::new(page)),
+            compressor,
+            previous_segment_file_names,
+            failed: false,
+        })
+    }
+
+    fn previous_page(&mut self) -> Result<Option<LogPage>, Error> {
+        let Some(block_id) = self.previous_block_id()? else {
+            return Ok(None);
+        };
+        let page = self.file_manager.read::<LogPage>(&block_id)?;
+        self.current_block_id = block_id;
+        Ok(Some(page))
+    }
+
+    fn previous_block_id(&mut self) -> Result<Option<BlockId>, Error> {
+        if let Some(previous_block_id) = self.current_block_id.previous() {
+            return Ok(Some(previous_block_id));
+        }
+        let Some(segment_file_name) = self.previous_segment_file_names.pop() else {
+            return Ok(None);
+        };
+        let number_of_blocks = self.file_manager.number_of_blocks(&segment_file_name)?;
+
+        Ok(number_of_blocks
+            .checked_sub(1)
+            .map(|last_block_number| BlockId::new(&segment_file_name, last_block_number)))
+    }
+}
+
+impl<PathType: AsRef<Path>> Iterator for ForwardLogIterator<'_, PathType> {
+    type Item = Result<Vec<u8>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.failed {
+                return None;
+            }
+            if let Some(record) = self.record_iterator.record() {
+                let decoded = match decode_sequenced_record(record) {
+                    Ok((log_sequence_number, _))
+                        if log_sequence_number < self.from_log_sequence_number =>
+                    {
+                        continue
+                    }
+                    Ok((_, record)) => decode_record(self.compressor.as_ref(), record),
+                    Err(err) => Err(err),
+                };
+                self.failed = decoded.is_err();
+                return Some(decoded);
+            }
+            if self.record_iterator.is_corrupted() {
+                return None;
+            }
+            match self.next_page() {
+                Ok(Some(page)) => self.record_iterator = ForwardRecordIterator::new(Arc::new(page)),
+                Ok(None) => return None,
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, PathType: AsRef<Path>> ForwardLogIterator<'a, PathType> {
+    pub(crate) fn new(
+        file_manager: &'a FileManager<PathType>,
+        segment_file_names: Vec<String>,
+        last_block_id: BlockId,
+        compressor: Arc<dyn Compressor>,
+        from_log_sequence_number: usize,
+    ) -> Result<ForwardLogIterator<'a, PathType>, Error> {
+        let mut next_segment_file_names = segment_file_names;
+        next_segment_file_names.reverse();
+        let first_segment_file_name = next_segment_file_names
+            .pop()
+            .unwrap_or_else(|| last_block_id.file_name().to_string());
+
+        let current_block_id = BlockId::new(&first_segment_file_name, 0);
+        let page = file_manager.read::<LogPage>(&current_block_id)?;
+
+        Ok(ForwardLogIterator {
+            file_manager,
+            current_block_id,
+            last_block_id,
+            record_iterator: ForwardRecordIterator::new(Arc::new(page)),
+            compressor,
+            next_segment_file_names,
+            from_log_sequence_number,
+            failed: false,
         })
     }
+
+    fn next_page(&mut self) -> Result<Option<LogPage>, Error> {
+        let Some(block_id) = self.next_block_id()? else {
+            return Ok(None);
+        };
+        let page = self.file_manager.read::<LogPage>(&block_id)?;
+        self.current_block_id = block_id;
+        Ok(Some(page))
+    }
+
+    fn next_block_id(&mut self) -> Result<Option<BlockId>, Error> {
+        if self.current_block_id == self.last_block_id {
+            return Ok(None);
+        }
+        let number_of_blocks = self
+            .file_manager
+            .number_of_blocks(self.current_block_id.file_name())?;
+        if self.current_block_id.block_number + 1 < number_of_blocks {
+            return Ok(Some(self.current_block_id.next()));
+        }
+        Ok(self
+            .next_segment_file_names
+            .pop()
+            .map(|segment_file_name| BlockId::new(&segment_file_name, 0)))
+    }
 }
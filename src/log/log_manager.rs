@@ -1,76 +1,493 @@
+use crate::error::Error;
 use crate::file::block_id::BlockId;
 use crate::file::file_manager::FileManager;
-use crate::log::iterator::BackwardLogIterator;
+use crate::log::compressor::{decode_record, encode_record, Compressor, NoOpCompressor};
+use crate::log::iterator::{BackwardLogIterator, ForwardLogIterator};
 use crate::log::page::LogPage;
+use crate::log::sequenced_record::{decode_sequenced_record, encode_sequenced_record};
 use std::io;
+use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
 
 pub(crate) struct LogManager<'a, PathType: AsRef<Path>> {
     file_manager: &'a FileManager<PathType>,
     log_file_name: String,
+    block_size: usize,
     log_page: LogPage,
     current_block_id: BlockId,
     latest_log_sequence_number: usize,
     last_saved_log_sequence_number: usize,
+    compressor: Arc<dyn Compressor>,
+    max_blocks_per_segment: Option<usize>,
+    segments: Vec<Segment>,
+}
+
+struct Segment {
+    segment_number: usize,
+    first_log_sequence_number: usize,
 }
 
 impl<'a, PathType: AsRef<Path>> LogManager<'a, PathType> {
     pub(crate) fn new(
         file_manager: &'a FileManager<PathType>,
         log_file_name: String,
-    ) -> Result<LogManager<'a, PathType>, io::Error> {
-        let number_of_blocks = file_manager.number_of_blocks(&log_file_name)?;
-        let (block_id, log_page) = match number_of_blocks {
-            0 => (
-                file_manager.append_empty_block(&log_file_name)?,
-                LogPage::new(file_manager.block_size),
-            ),
-            _ => {
-                let block_id = BlockId::new(&log_file_name, number_of_blocks - 1);
-                let page = file_manager.read::<LogPage>(&block_id)?;
-                (block_id, page)
+    ) -> Result<LogManager<'a, PathType>, Error> {
+        Self::create(
+            file_manager,
+            log_file_name,
+            file_manager.block_size,
+            Arc::new(NoOpCompressor),
+            None,
+        )
+    }
+
+    pub(crate) fn new_with_block_size(
+        file_manager: &'a FileManager<PathType>,
+        log_file_name: String,
+        block_size: usize,
+    ) -> Result<LogManager<'a, PathType>, Error> {
+        Self::create(
+            file_manager,
+            log_file_name,
+            block_size,
+            Arc::new(NoOpCompressor),
+            None,
+        )
+    }
+
+    pub(crate) fn new_with_compressor(
+        file_manager: &'a FileManager<PathType>,
+        log_file_name: String,
+        compressor: Arc<dyn Compressor>,
+    ) -> Result<LogManager<'a, PathType>, Error> {
+        Self::create(
+            file_manager,
+            log_file_name,
+            file_manager.block_size,
+            compressor,
+            None,
+        )
+    }
+
+    pub(crate) fn new_with_segments(
+        file_manager: &'a FileManager<PathType>,
+        log_file_name: String,
+        max_blocks_per_segment: usize,
+    ) -> Result<LogManager<'a, PathType>, Error> {
+        Self::create(
+            file_manager,
+            log_file_name,
+            file_manager.block_size,
+            Arc::new(NoOpCompressor),
+            Some(max_blocks_per_segment),
+        )
+    }
+
+    fn create(
+        file_manager: &'a FileManager<PathType>,
+        log_file_name: String,
+        block_size: usize,
+        compressor: Arc<dyn Compressor>,
+        max_blocks_per_segment: Option<usize>,
+    ) -> Result<LogManager<'a, PathType>, Error> {
+        let mut segments = match max_blocks_per_segment {
+            Some(_) => Self::existing_segments(file_manager, &log_file_name, block_size)?,
+            None => {
+                file_manager.use_block_size_for(&log_file_name, block_size)?;
+                vec![Segment::new(0, 0)]
             }
         };
+        let active_segment_file_name = Self::segment_file_name_for(
+            &log_file_name,
+            max_blocks_per_segment,
+            segments[segments.len() - 1].segment_number,
+        );
+
+        // Preallocated blocks are empty, so appends resume after the last block holding records.
+        let (block_id, log_page) =
+            match Self::last_non_empty_block(file_manager, &active_segment_file_name)? {
+                Some(last_non_empty_block) => last_non_empty_block,
+                None if file_manager.number_of_blocks(&active_segment_file_name)? == 0 => (
+                    file_manager.append_empty_block(&active_segment_file_name)?,
+                    LogPage::new(block_size),
+                ),
+                None => (
+                    BlockId::new(&active_segment_file_name, 0),
+                    LogPage::new(block_size),
+                ),
+            };
+        let latest_log_sequence_number = match log_page.last_record() {
+            Some(record) => decode_sequenced_record(record)?.0,
+            None => Self::last_log_sequence_number_in(
+                file_manager,
+                &log_file_name,
+                max_blocks_per_segment,
+                &segments[..segments.len() - 1],
+            )?,
+        };
+        let active_segment = segments.len() - 1;
+        if segments[active_segment].first_log_sequence_number == usize::MAX {
+            segments[active_segment].first_log_sequence_number = latest_log_sequence_number + 1;
+        }
         Ok(LogManager {
             file_manager,
             log_file_name,
+            block_size,
             log_page,
             current_block_id: block_id,
-            latest_log_sequence_number: 0,     //TODO: revisit
-            last_saved_log_sequence_number: 0, //TODO: revisit
+            latest_log_sequence_number,
+            last_saved_log_sequence_number: latest_log_sequence_number,
+            compressor,
+            max_blocks_per_segment,
+            segments,
         })
     }
 
-    fn append(&mut self, buffer: &[u8]) -> Result<(), io::Error> {
-        if !self.log_page.add(buffer) {
-            self.force_flush()?;
-            self.current_block_id = self
-                .file_manager
-                .append_empty_block(self.log_file_name.as_ref())?;
-            self.log_page = LogPage::new(self.file_manager.block_size);
-            assert!(self.log_page.add(buffer));
+    pub(crate) fn append(&mut self, buffer: &[u8]) -> Result<usize, Error> {
+        let buffer = encode_sequenced_record(
+            self.latest_log_sequence_number + 1,
+            &encode_record(self.compressor.as_ref(), buffer),
+        );
+        self.append_encoded(&buffer)
+    }
+
+    /// Appends the record and returns its log sequence number along with the block and the
+    /// in-block offset it was written at, which [`LogManager::record_at`] accepts.
+    pub(crate) fn append_located(&mut self, buffer: &[u8]) -> Result<(usize, BlockId, u32), Error> {
+        let log_sequence_number = self.append(buffer)?;
+        let offset = self
+            .log_page
+            .last_record_offset()
+            .expect("the current log page holds the appended record");
+        Ok((log_sequence_number, self.current_block_id.clone(), offset))
+    }
+
+    pub(crate) fn append_batch(&mut self, records: &[&[u8]]) -> Result<Vec<usize>, Error> {
+        let encoded_records: Vec<Vec<u8>> = records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                encode_sequenced_record(
+                    self.latest_log_sequence_number + 1 + index,
+                    &encode_record(self.compressor.as_ref(), record),
+                )
+            })
+            .collect();
+
+        for record in &encoded_records {
+            self.ensure_fits_in_an_empty_page(record)?;
         }
-        self.latest_log_sequence_number += 1;
-        Ok(())
+
+        // Packs the current page with every record that fits before rolling, so the batch is
+        // flushed once per filled block rather than once per record.
+        let mut log_sequence_numbers = Vec::with_capacity(encoded_records.len());
+        for record in &encoded_records {
+            if !self.log_page.add(record) {
+                self.roll_to_next_block()?;
+                if !self.log_page.add(record) {
+                    return Err(Error::RecordTooLarge {
+                        length: record.len(),
+                        block_size: self.block_size,
+                    });
+                }
+            }
+            self.latest_log_sequence_number += 1;
+            log_sequence_numbers.push(self.latest_log_sequence_number);
+        }
+        Ok(log_sequence_numbers)
+    }
+
+    pub(crate) fn backward_iterator(&mut self) -> Result<BackwardLogIterator<'a, PathType>, Error> {
+        self.force_flush()?;
+        let previous_segment_file_names = self.segments[..self.segments.len() - 1]
+            .iter()
+            .map(|segment| self.segment_file_name(segment.segment_number))
+            .collect();
+
+        BackwardLogIterator::new(
+            self.file_manager,
+            self.current_block_id.clone(),
+            self.compressor.clone(),
+            previous_segment_file_names,
+        )
+    }
+
+    pub(crate) fn forward_iterator(&mut self) -> Result<ForwardLogIterator<'a, PathType>, Error> {
+        self.iterate_from(0)
     }
 
-    fn backward_iterator(&mut self) -> Result<BackwardLogIterator<PathType>, io::Error> {
+    pub(crate) fn iterate_from(
+        &mut self,
+        log_sequence_number: usize,
+    ) -> Result<ForwardLogIterator<'a, PathType>, Error> {
         self.force_flush()?;
-        BackwardLogIterator::new(self.file_manager, self.current_block_id.clone())
+        let segment_file_names = self
+            .segments
+            .iter()
+            .map(|segment| self.segment_file_name(segment.segment_number))
+            .collect();
+
+        ForwardLogIterator::new(
+            self.file_manager,
+            segment_file_names,
+            self.current_block_id.clone(),
+            self.compressor.clone(),
+            log_sequence_number,
+        )
+    }
+
+    /// Decodes the records of a single block, oldest first. Records of the current block that
+    /// are not flushed yet are served from memory.
+    pub(crate) fn records_in_block(&self, block_id: &BlockId) -> Result<Vec<Vec<u8>>, Error> {
+        self.with_page(block_id, |page| {
+            (0..page.number_of_records())
+                .map_while(|index| page.record_at(index))
+                .map(|record| self.decoded_record(record))
+                .collect()
+        })?
     }
 
-    pub(crate) fn flush(&mut self, log_sequence_number: usize) -> Result<(), io::Error> {
+    pub(crate) fn record_at(
+        &self,
+        block_id: &BlockId,
+        offset: u32,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        self.with_page(block_id, |page| {
+            page.record_starting_at(offset)
+                .map(|record| self.decoded_record(record))
+                .transpose()
+        })?
+    }
+
+    pub(crate) fn truncate_before(&mut self, log_sequence_number: usize) -> Result<(), Error> {
+        while self.segments.len() > 1
+            && self.segments[1].first_log_sequence_number <= log_sequence_number
+        {
+            let segment = self.segments.remove(0);
+            self.file_manager
+                .delete_file(&self.segment_file_name(segment.segment_number))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self, log_sequence_number: usize) -> Result<(), Error> {
         if log_sequence_number >= self.last_saved_log_sequence_number {
             self.force_flush()?
         }
         Ok(())
     }
 
-    pub(crate) fn file_manager(&self) -> &FileManager<PathType> {
+    /// Flushes the records that are not written yet. Dropping the manager flushes as well but
+    /// cannot report a failure, so callers that need to know use `close`.
+    pub(crate) fn close(mut self) -> Result<(), Error> {
+        self.flush_unsaved_records()
+    }
+
+    pub(crate) fn preallocate(&self, number_of_blocks: usize) -> Result<(), Error> {
+        self.file_manager.preallocate(
+            self.current_block_id.file_name(),
+            self.current_block_id.block_number + 1 + number_of_blocks,
+        )
+    }
+
+    pub(crate) fn file_manager(&self) -> &'a FileManager<PathType> {
+        self.file_manager
+    }
+
+    fn with_page<T>(&self, block_id: &BlockId, f: impl FnOnce(&LogPage) -> T) -> Result<T, Error> {
+        if *block_id == self.current_block_id {
+            return Ok(f(&self.log_page));
+        }
+        Ok(f(&self.file_manager.read::<LogPage>(block_id)?))
+    }
+
+    fn decoded_record(&self, record: &[u8]) -> Result<Vec<u8>, Error> {
+        let (_, record) = decode_sequenced_record(record)?;
+        decode_record(self.compressor.as_ref(), record)
+    }
+
+    fn append_encoded(&mut self, buffer: &[u8]) -> Result<usize, Error> {
+        self.ensure_fits_in_an_empty_page(buffer)?;
+        if !self.log_page.add(buffer) {
+            self.roll_to_next_block()?;
+            if !self.log_page.add(buffer) {
+                return Err(Error::RecordTooLarge {
+                    length: buffer.len(),
+                    block_size: self.block_size,
+                });
+            }
+        }
+        self.latest_log_sequence_number += 1;
+        Ok(self.latest_log_sequence_number)
+    }
+
+    fn roll_to_next_block(&mut self) -> Result<(), Error> {
+        self.force_flush()?;
+        self.current_block_id = self.next_block()?;
+        // The flushed page is only reused once the next block exists, so a failed roll leaves
+        // the current page as it was.
+        self.log_page.reset();
+        Ok(())
+    }
+
+    fn ensure_fits_in_an_empty_page(&self, buffer: &[u8]) -> Result<(), Error> {
+        if !LogPage::fits_in_an_empty_page(self.block_size, buffer) {
+            return Err(Error::RecordTooLarge {
+                length: buffer.len(),
+                block_size: self.block_size,
+            });
+        }
+        Ok(())
+    }
+
+    // Returns the block after the current one: the next preallocated block, a new block at the
+    // end of the active segment, or the first block of a new segment. The new block stays empty
+    // on disk until the page written into it is flushed.
+    fn next_block(&mut self) -> Result<BlockId, Error> {
+        let rotate = self
+            .max_blocks_per_segment
+            .is_some_and(|max_blocks| self.current_block_id.block_number + 1 >= max_blocks);
+
+        let active_segment_number = self.segments[self.segments.len() - 1].segment_number;
+        if !rotate {
+            let active_segment_file_name = self.segment_file_name(active_segment_number);
+            let next_block_id = self.current_block_id.next();
+            if next_block_id.block_number
+                < self
+                    .file_manager
+                    .number_of_blocks(&active_segment_file_name)?
+            {
+                return Ok(next_block_id);
+            }
+            return self
+                .file_manager
+                .append_empty_block(&active_segment_file_name);
+        }
+
+        let segment_file_name = self.segment_file_name(active_segment_number + 1);
         self.file_manager
+            .use_block_size_for(&segment_file_name, self.block_size)?;
+        let block_id = self.file_manager.append_empty_block(&segment_file_name)?;
+        self.segments.push(Segment::new(
+            active_segment_number + 1,
+            self.latest_log_sequence_number + 1,
+        ));
+        Ok(block_id)
     }
 
-    fn force_flush(&mut self) -> Result<(), io::Error> {
+    fn segment_file_name(&self, segment_number: usize) -> String {
+        Self::segment_file_name_for(
+            &self.log_file_name,
+            self.max_blocks_per_segment,
+            segment_number,
+        )
+    }
+
+    fn segment_file_name_for(
+        log_file_name: &str,
+        max_blocks_per_segment: Option<usize>,
+        segment_number: usize,
+    ) -> String {
+        match max_blocks_per_segment {
+            Some(_) => Self::numbered_segment_file_name(log_file_name, segment_number),
+            None => log_file_name.to_string(),
+        }
+    }
+
+    fn numbered_segment_file_name(log_file_name: &str, segment_number: usize) -> String {
+        format!("{}.{:06}", log_file_name, segment_number)
+    }
+
+    fn existing_segments(
+        file_manager: &FileManager<PathType>,
+        log_file_name: &str,
+        block_size: usize,
+    ) -> Result<Vec<Segment>, Error> {
+        let prefix = format!("{}.", log_file_name);
+        let mut segment_numbers: Vec<usize> = file_manager
+            .file_names()?
+            .iter()
+            .filter_map(|file_name| file_name.strip_prefix(&prefix))
+            .filter(|suffix| suffix.len() == 6)
+            .filter_map(|suffix| suffix.parse().ok())
+            .collect();
+
+        segment_numbers.sort();
+        if segment_numbers.is_empty() {
+            segment_numbers.push(0);
+        }
+        segment_numbers
+            .into_iter()
+            .map(|segment_number| {
+                let segment_file_name =
+                    Self::numbered_segment_file_name(log_file_name, segment_number);
+                file_manager.use_block_size_for(&segment_file_name, block_size)?;
+                let first_log_sequence_number =
+                    Self::first_log_sequence_number_in(file_manager, &segment_file_name)?;
+                Ok(Segment::new(segment_number, first_log_sequence_number))
+            })
+            .collect()
+    }
+
+    // A segment without records makes no earlier segment obsolete, so it is never the reason
+    // for a truncation.
+    fn first_log_sequence_number_in(
+        file_manager: &FileManager<PathType>,
+        file_name: &str,
+    ) -> Result<usize, Error> {
+        for block_number in 0..file_manager.number_of_blocks(file_name)? {
+            let page = file_manager.read::<LogPage>(&BlockId::new(file_name, block_number))?;
+            if let Some(record) = page.record_at(0) {
+                return Ok(decode_sequenced_record(record)?.0);
+            }
+        }
+        Ok(usize::MAX)
+    }
+
+    fn flush_unsaved_records(&mut self) -> Result<(), Error> {
+        if self.latest_log_sequence_number > self.last_saved_log_sequence_number {
+            self.force_flush()?;
+        }
+        Ok(())
+    }
+
+    fn last_non_empty_block(
+        file_manager: &FileManager<PathType>,
+        file_name: &str,
+    ) -> Result<Option<(BlockId, LogPage)>, Error> {
+        for block_number in (0..file_manager.number_of_blocks(file_name)?).rev() {
+            let block_id = BlockId::new(file_name, block_number);
+            let page = file_manager.read::<LogPage>(&block_id)?;
+            if !page.is_empty() {
+                return Ok(Some((block_id, page)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn last_log_sequence_number_in(
+        file_manager: &FileManager<PathType>,
+        log_file_name: &str,
+        max_blocks_per_segment: Option<usize>,
+        segments: &[Segment],
+    ) -> Result<usize, Error> {
+        for segment in segments.iter().rev() {
+            let segment_file_name = Self::segment_file_name_for(
+                log_file_name,
+                max_blocks_per_segment,
+                segment.segment_number,
+            );
+            if let Some((_, page)) = Self::last_non_empty_block(file_manager, &segment_file_name)? {
+                if let Some(record) = page.last_record() {
+                    return Ok(decode_sequenced_record(record)?.0);
+                }
+            }
+        }
+        Ok(0)
+    }
+
+    fn force_flush(&mut self) -> Result<(), Error> {
         self.file_manager
             .write(&self.current_block_id, self.log_page.encode())?;
         self.last_saved_log_sequence_number = self.latest_log_sequence_number;
@@ -78,10 +495,42 @@ impl<'a, PathType: AsRef<Path>> LogManager<'a, PathType> {
     }
 }
 
+impl<PathType: AsRef<Path>> Write for LogManager<'_, PathType> {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.append(buffer)?;
+        Ok(buffer.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(self.force_flush()?)
+    }
+}
+
+impl<PathType: AsRef<Path>> Drop for LogManager<'_, PathType> {
+    fn drop(&mut self) {
+        // Drop cannot report the failure; `close` does.
+        let _ = self.flush_unsaved_records();
+    }
+}
+
+impl Segment {
+    fn new(segment_number: usize, first_log_sequence_number: usize) -> Self {
+        Segment {
+            segment_number,
+            first_log_sequence_number,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
+    use crate::file::block_id::BlockId;
     use crate::file::file_manager::FileManager;
+    use crate::log::compressor::RunLengthCompressor;
     use crate::log::log_manager::LogManager;
+    use std::io::Write;
+    use std::sync::Arc;
     use tempfile::NamedTempFile;
 
     const BLOCK_SIZE: usize = 4096;
@@ -100,6 +549,92 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn append_empty_and_non_empty_records_in_log_and_reload() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let log_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        assert_eq!(1, log_manager.append(b"").unwrap());
+        assert_eq!(2, log_manager.append(b"RocksDB").unwrap());
+        assert_eq!(3, log_manager.append(b"").unwrap());
+        log_manager.force_flush().unwrap();
+        drop(log_manager);
+
+        let mut reloaded_log_manager =
+            LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let records: Vec<Vec<u8>> = reloaded_log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![b"".to_vec(), b"RocksDB".to_vec(), b"".to_vec()],
+            records
+        );
+    }
+
+    #[test]
+    fn collect_records_until_a_sentinel_record_in_both_directions() {
+        const SENTINEL: &[u8] = b"checkpoint";
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let log_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let file_manager = FileManager::new(directory_path, 128).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+
+        log_manager.append(b"RocksDB").unwrap();
+        log_manager.append(b"PebbleDB").unwrap();
+        log_manager.append(SENTINEL).unwrap();
+        log_manager.append(b"BoltDB").unwrap();
+        log_manager.append(b"LMDB").unwrap();
+        assert!(file_manager.number_of_blocks(log_file_name).unwrap() > 1);
+
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .map(Result::unwrap)
+            .take_while(|record| record != SENTINEL)
+            .collect();
+        assert_eq!(vec![b"RocksDB".to_vec(), b"PebbleDB".to_vec()], records);
+
+        let records: Vec<Vec<u8>> = log_manager
+            .backward_iterator()
+            .unwrap()
+            .map(Result::unwrap)
+            .take_while(|record| record != SENTINEL)
+            .collect();
+        assert_eq!(vec![b"LMDB".to_vec(), b"BoltDB".to_vec()], records);
+    }
+
+    #[test]
+    fn attempt_to_append_a_record_larger_than_a_log_page() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        assert_eq!(1, log_manager.append(b"RocksDB").unwrap());
+        assert!(matches!(
+            log_manager.append(&[b'r'; BLOCK_SIZE_IN_BYTES]),
+            Err(Error::RecordTooLarge {
+                block_size: BLOCK_SIZE_IN_BYTES,
+                ..
+            })
+        ));
+        assert!(matches!(
+            log_manager.append(&[b'r'; BLOCK_SIZE_IN_BYTES]),
+            Err(Error::RecordTooLarge { .. })
+        ));
+        assert_eq!(2, log_manager.append(b"PebbleDB").unwrap());
+        assert_eq!(1, file_manager.number_of_blocks(file_name).unwrap());
+    }
+
     #[test]
     fn append_a_record_in_log_and_iterate_over_it() {
         let file = NamedTempFile::new().expect("Failed to create temp file");
@@ -116,9 +651,9 @@ mod tests {
         let mut iterator = log_manager.backward_iterator().unwrap();
         assert_eq!(
             b"RocksDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
-        assert_eq!(None, iterator.next());
+        assert!(iterator.next().is_none());
     }
 
     #[test]
@@ -143,17 +678,17 @@ mod tests {
         let mut iterator = log_manager.backward_iterator().unwrap();
         assert_eq!(
             b"BoltDB is a B+Tree storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
         assert_eq!(
             b"PebbleDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
         assert_eq!(
             b"RocksDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
-        assert_eq!(None, iterator.next());
+        assert!(iterator.next().is_none());
     }
 
     #[test]
@@ -179,17 +714,17 @@ mod tests {
         let mut iterator = log_manager.backward_iterator().unwrap();
         assert_eq!(
             b"BoltDB is a B+Tree storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
         assert_eq!(
             b"PebbleDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
         assert_eq!(
             b"RocksDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
-        assert_eq!(None, iterator.next());
+        assert!(iterator.next().is_none());
     }
 
     #[test]
@@ -219,16 +754,861 @@ mod tests {
         let mut iterator = reloaded_log_manager.backward_iterator().unwrap();
         assert_eq!(
             b"BoltDB is a B+Tree storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
         );
         assert_eq!(
             b"PebbleDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
+        );
+        assert_eq!(
+            b"RocksDB is an LSM-based storage engine".to_vec(),
+            iterator.next().unwrap().unwrap()
+        );
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn append_records_into_the_same_block_after_a_reload() {
+        const LOG_BLOCK_SIZE_IN_BYTES: usize = 128;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let log_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+
+        let records_in_a_block = {
+            let other_file = NamedTempFile::new().expect("Failed to create temp file");
+            let other_log_file_name = other_file.path().file_name().unwrap().to_str().unwrap();
+            let mut log_manager = LogManager::new_with_block_size(
+                &file_manager,
+                other_log_file_name.to_string(),
+                LOG_BLOCK_SIZE_IN_BYTES,
+            )
+            .unwrap();
+            let mut records_appended = 0;
+            while file_manager.number_of_blocks(other_log_file_name).unwrap() == 1 {
+                records_appended += 1;
+                log_manager
+                    .append(format!("Record {}", records_appended).as_bytes())
+                    .unwrap();
+            }
+            records_appended - 1
+        };
+        assert!(records_in_a_block > 2);
+
+        let mut log_manager = LogManager::new_with_block_size(
+            &file_manager,
+            log_file_name.to_string(),
+            LOG_BLOCK_SIZE_IN_BYTES,
+        )
+        .unwrap();
+        (1..=2).for_each(|record_id| {
+            log_manager
+                .append(format!("Record {}", record_id).as_bytes())
+                .unwrap();
+        });
+        log_manager.force_flush().unwrap();
+        drop(log_manager);
+
+        let mut reloaded_log_manager = LogManager::new_with_block_size(
+            &file_manager,
+            log_file_name.to_string(),
+            LOG_BLOCK_SIZE_IN_BYTES,
+        )
+        .unwrap();
+        let mut records_appended = 2;
+        while file_manager.number_of_blocks(log_file_name).unwrap() == 1 {
+            records_appended += 1;
+            reloaded_log_manager
+                .append(format!("Record {}", records_appended).as_bytes())
+                .unwrap();
+        }
+        assert_eq!(records_in_a_block, records_appended - 1);
+
+        let records: Vec<Vec<u8>> = reloaded_log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (1..=records_appended)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn append_records_in_log_with_a_block_size_different_from_the_data_and_reload() {
+        const LOG_BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let log_file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut log_manager = LogManager::new_with_block_size(
+            &file_manager,
+            log_file_name.to_string(),
+            LOG_BLOCK_SIZE_IN_BYTES,
+        )
+        .unwrap();
+
+        assert!(log_manager
+            .append(b"RocksDB is an LSM-based storage engine")
+            .is_ok());
+        assert!(log_manager
+            .append(b"PebbleDB is an LSM-based storage engine")
+            .is_ok());
+        assert!(log_manager.force_flush().is_ok());
+        drop(log_manager);
+
+        let reloaded_file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut reloaded_log_manager = LogManager::new_with_block_size(
+            &reloaded_file_manager,
+            log_file_name.to_string(),
+            LOG_BLOCK_SIZE_IN_BYTES,
+        )
+        .unwrap();
+        assert_eq!(
+            2,
+            reloaded_file_manager
+                .number_of_blocks(log_file_name)
+                .unwrap()
         );
+
+        assert_eq!(
+            3,
+            reloaded_log_manager
+                .append(b"BoltDB is a B+Tree storage engine")
+                .unwrap()
+        );
+
+        let mut iterator = reloaded_log_manager.forward_iterator().unwrap();
         assert_eq!(
             b"RocksDB is an LSM-based storage engine".to_vec(),
-            iterator.next().unwrap()
+            iterator.next().unwrap().unwrap()
+        );
+        assert_eq!(
+            b"PebbleDB is an LSM-based storage engine".to_vec(),
+            iterator.next().unwrap().unwrap()
+        );
+        assert_eq!(
+            b"BoltDB is a B+Tree storage engine".to_vec(),
+            iterator.next().unwrap().unwrap()
+        );
+        assert!(iterator.next().is_none());
+        assert_eq!(
+            3,
+            reloaded_file_manager
+                .number_of_blocks(log_file_name)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn append_a_few_compressible_records_and_iterate_over_them() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let log_file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut log_manager = LogManager::new_with_compressor(
+            &file_manager,
+            log_file_name.to_string(),
+            Arc::new(RunLengthCompressor),
+        )
+        .unwrap();
+
+        let records: Vec<Vec<u8>> = (0..50u8)
+            .map(|record_id| vec![b'a' + record_id % 26; 200])
+            .collect();
+        records.iter().for_each(|record| {
+            assert!(log_manager.append(record).is_ok());
+        });
+
+        let iterator = log_manager.backward_iterator().unwrap();
+        let iterated: Vec<Vec<u8>> = iterator.collect::<Result<_, _>>().unwrap();
+        assert_eq!(records.into_iter().rev().collect::<Vec<_>>(), iterated);
+        assert_eq!(1, file_manager.number_of_blocks(log_file_name).unwrap());
+    }
+
+    #[test]
+    fn append_records_across_segments_and_iterate_over_them() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+
+        (1..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+
+        let mut file_names = file_manager.file_names().unwrap();
+        file_names.sort();
+        assert_eq!(
+            vec!["wal.000000".to_string(), "wal.000001".to_string()],
+            file_names
+        );
+
+        let mut iterator = log_manager.backward_iterator().unwrap();
+        (1..=12).rev().for_each(|record_id| {
+            let record = format!("Record {}", record_id);
+            assert_eq!(
+                record.as_bytes().to_vec(),
+                iterator.next().unwrap().unwrap()
+            );
+        });
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn reopen_a_segmented_log_and_continue_in_the_latest_segment() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+        (1..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+        assert!(log_manager.force_flush().is_ok());
+        drop(log_manager);
+
+        let mut reloaded_log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+        assert!(reloaded_log_manager.append(b"Record 13").is_ok());
+
+        let mut iterator = reloaded_log_manager.backward_iterator().unwrap();
+        (1..=13).rev().for_each(|record_id| {
+            let record = format!("Record {}", record_id);
+            assert_eq!(
+                record.as_bytes().to_vec(),
+                iterator.next().unwrap().unwrap()
+            );
+        });
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn truncate_segments_before_a_log_sequence_number() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+
+        let log_sequence_numbers: Vec<usize> = (1..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .map(|record| log_manager.append(record.as_bytes()).unwrap())
+            .collect();
+
+        assert!(log_manager
+            .truncate_before(log_sequence_numbers[11])
+            .is_ok());
+        assert_eq!(
+            vec!["wal.000001".to_string()],
+            file_manager.file_names().unwrap()
+        );
+
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (7..=12)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn truncate_segments_of_a_reopened_log() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        {
+            let mut log_manager =
+                LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+            (1..=12).for_each(|record_id| {
+                log_manager
+                    .append(format!("Record {}", record_id).as_bytes())
+                    .unwrap();
+            });
+        }
+
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+        assert!(log_manager.truncate_before(1).is_ok());
+        assert!(log_manager.truncate_before(6).is_ok());
+        assert_eq!(12, log_manager.forward_iterator().unwrap().count());
+
+        assert!(log_manager.truncate_before(7).is_ok());
+        assert_eq!(
+            vec!["wal.000001".to_string()],
+            file_manager.file_names().unwrap()
+        );
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (7..=12)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn write_records_through_the_write_trait_and_iterate_over_them() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        assert!(log_manager
+            .write_all(b"RocksDB is an LSM-based storage engine")
+            .is_ok());
+        assert!(log_manager
+            .write_all(b"BoltDB is a B+Tree storage engine")
+            .is_ok());
+        assert!(Write::flush(&mut log_manager).is_ok());
+
+        let mut iterator = log_manager.backward_iterator().unwrap();
+        assert_eq!(
+            b"BoltDB is a B+Tree storage engine".to_vec(),
+            iterator.next().unwrap().unwrap()
+        );
+        assert_eq!(
+            b"RocksDB is an LSM-based storage engine".to_vec(),
+            iterator.next().unwrap().unwrap()
+        );
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn append_a_batch_of_records_and_iterate_over_them() {
+        const BLOCK_SIZE_IN_BYTES: usize = 256;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        let records: Vec<String> = (1..=1000)
+            .map(|record_id| format!("Record {}", record_id))
+            .collect();
+        let record_references: Vec<&[u8]> =
+            records.iter().map(|record| record.as_bytes()).collect();
+
+        let log_sequence_numbers = log_manager.append_batch(&record_references).unwrap();
+        assert_eq!((1..=1000).collect::<Vec<usize>>(), log_sequence_numbers);
+
+        let iterator = log_manager.backward_iterator().unwrap();
+        let mut iterated: Vec<Vec<u8>> = iterator.collect::<Result<_, _>>().unwrap();
+        iterated.reverse();
+
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.as_bytes().to_vec())
+                .collect::<Vec<_>>(),
+            iterated
+        );
+    }
+
+    #[test]
+    fn append_a_batch_of_records_with_a_write_per_rolled_block() {
+        const BLOCK_SIZE_IN_BYTES: usize = 256;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        let records: Vec<String> = (1..=100)
+            .map(|record_id| format!("Record {}", record_id))
+            .collect();
+        let record_references: Vec<&[u8]> =
+            records.iter().map(|record| record.as_bytes()).collect();
+
+        let writes = file_manager.io_stats().writes;
+        log_manager.append_batch(&record_references).unwrap();
+
+        // Each roll flushes the filled page and appends the next block; nothing else is written.
+        let rolled_blocks = file_manager.number_of_blocks(file_name).unwrap() - 1;
+        assert!(rolled_blocks > 1);
+        assert_eq!(2 * rolled_blocks, file_manager.io_stats().writes - writes);
+    }
+
+    #[test]
+    fn attempt_to_append_a_batch_with_a_record_larger_than_a_log_page() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        let large_record = [b'r'; BLOCK_SIZE_IN_BYTES];
+        assert!(matches!(
+            log_manager.append_batch(&[b"RocksDB", &large_record]),
+            Err(Error::RecordTooLarge { .. })
+        ));
+        assert!(log_manager.backward_iterator().unwrap().next().is_none());
+    }
+
+    #[test]
+    fn append_a_few_records_in_log_with_smaller_block_size_and_iterate_forward() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        (1..=20)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+        assert!(file_manager.number_of_blocks(file_name).unwrap() > 1);
+
+        let mut iterator = log_manager.forward_iterator().unwrap();
+        (1..=20).for_each(|record_id| {
+            let record = format!("Record {}", record_id);
+            assert_eq!(
+                record.as_bytes().to_vec(),
+                iterator.next().unwrap().unwrap()
+            );
+        });
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn report_a_corrupt_block_while_iterating() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        (1..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+        assert_eq!(4, file_manager.number_of_blocks(file_name).unwrap());
+
+        let corrupt_block_id = BlockId::new(file_name, 1);
+        let mut block = vec![0; BLOCK_SIZE_IN_BYTES];
+        file_manager
+            .read_into(&corrupt_block_id, &mut block)
+            .unwrap();
+        block[4] ^= 0xFF;
+        file_manager.write(&corrupt_block_id, &block).unwrap();
+
+        let mut iterator = log_manager.forward_iterator().unwrap();
+        (1..=3).for_each(|record_id| {
+            let record = format!("Record {}", record_id);
+            assert_eq!(
+                record.as_bytes().to_vec(),
+                iterator.next().unwrap().unwrap()
+            );
+        });
+        assert!(matches!(iterator.next(), Some(Err(_))));
+        assert!(iterator.next().is_none());
+
+        let mut iterator = log_manager.backward_iterator().unwrap();
+        (7..=12).rev().for_each(|record_id| {
+            let record = format!("Record {}", record_id);
+            assert_eq!(
+                record.as_bytes().to_vec(),
+                iterator.next().unwrap().unwrap()
+            );
+        });
+        assert!(matches!(iterator.next(), Some(Err(_))));
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn iterate_forward_across_segments() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+
+        (1..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (1..=12)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn keep_the_current_page_when_rolling_to_a_new_segment_fails() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+
+        (1..=6)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+
+        let blocking_directory = directory.path().join("wal.000001");
+        std::fs::create_dir(&blocking_directory).unwrap();
+        assert!(log_manager.append(b"Record 7").is_err());
+        std::fs::remove_dir(&blocking_directory).unwrap();
+
+        (7..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (1..=12)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn report_a_segment_that_cannot_be_opened_while_iterating_backward() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let directory = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let file_manager = FileManager::new(directory.path(), BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager =
+            LogManager::new_with_segments(&file_manager, "wal".to_string(), 2).unwrap();
+
+        (1..=12)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+
+        let mut iterator = log_manager.backward_iterator().unwrap();
+        log_manager.truncate_before(7).unwrap();
+        std::fs::create_dir(directory.path().join("wal.000000")).unwrap();
+
+        (7..=12).rev().for_each(|record_id| {
+            let record = format!("Record {}", record_id);
+            assert_eq!(
+                record.as_bytes().to_vec(),
+                iterator.next().unwrap().unwrap()
+            );
+        });
+        assert!(matches!(iterator.next(), Some(Err(_))));
+        assert!(iterator.next().is_none());
+    }
+
+    #[test]
+    fn iterate_from_a_log_sequence_number() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        let log_sequence_numbers: Vec<usize> = (1..=10)
+            .map(|record_id| {
+                log_manager
+                    .append(format!("Record {}", record_id).as_bytes())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!((1..=10).collect::<Vec<_>>(), log_sequence_numbers);
+
+        let records: Vec<Vec<u8>> = log_manager
+            .iterate_from(5)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (5..=10)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn continue_log_sequence_numbers_after_a_reload() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        {
+            let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+            log_manager.append(b"RocksDB").unwrap();
+            log_manager.append(b"PebbleDB").unwrap();
+            log_manager.flush(2).unwrap();
+        }
+
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        assert_eq!(3, log_manager.append(b"BoltDB").unwrap());
+
+        let records: Vec<Vec<u8>> = log_manager
+            .iterate_from(2)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![b"PebbleDB".to_vec(), b"BoltDB".to_vec()], records);
+    }
+
+    #[test]
+    fn append_records_into_preallocated_blocks() {
+        const BLOCK_SIZE_IN_BYTES: usize = 100;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        log_manager.preallocate(10).unwrap();
+        assert_eq!(11, file_manager.number_of_blocks(file_name).unwrap());
+
+        (1..=20)
+            .map(|record_id| format!("Record {}", record_id))
+            .for_each(|record| {
+                assert!(log_manager.append(record.as_bytes()).is_ok());
+            });
+        assert_eq!(11, file_manager.number_of_blocks(file_name).unwrap());
+
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            (1..=20)
+                .map(|record_id| format!("Record {}", record_id).into_bytes())
+                .collect::<Vec<_>>(),
+            records
+        );
+    }
+
+    #[test]
+    fn read_records_of_a_single_block() {
+        const BLOCK_SIZE_IN_BYTES: usize = 128;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new_with_compressor(
+            &file_manager,
+            file_name.to_string(),
+            Arc::new(RunLengthCompressor),
+        )
+        .unwrap();
+
+        log_manager
+            .append(b"RocksDB is an LSM-based storage engine")
+            .unwrap();
+        log_manager
+            .append(b"PebbleDB is an LSM-based storage engine")
+            .unwrap();
+        log_manager
+            .append(b"BoltDB is a B+Tree storage engine")
+            .unwrap();
+        assert_eq!(2, file_manager.number_of_blocks(file_name).unwrap());
+
+        assert_eq!(
+            vec![b"BoltDB is a B+Tree storage engine".to_vec()],
+            log_manager
+                .records_in_block(&BlockId::new(file_name, 1))
+                .unwrap()
+        );
+        assert_eq!(
+            vec![
+                b"RocksDB is an LSM-based storage engine".to_vec(),
+                b"PebbleDB is an LSM-based storage engine".to_vec()
+            ],
+            log_manager
+                .records_in_block(&BlockId::new(file_name, 0))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn read_a_record_at_the_location_it_was_appended() {
+        const BLOCK_SIZE_IN_BYTES: usize = 128;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        let locations: Vec<(usize, BlockId, u32)> = [
+            b"RocksDB is an LSM-based storage engine".as_slice(),
+            b"PebbleDB is an LSM-based storage engine",
+            b"BoltDB is a B+Tree storage engine",
+        ]
+        .iter()
+        .map(|record| log_manager.append_located(record).unwrap())
+        .collect();
+
+        assert_eq!((1, BlockId::new(file_name, 0), 0), locations[0]);
+        assert_eq!(2, locations[1].0);
+        assert_eq!(BlockId::new(file_name, 1), locations[2].1);
+
+        let (_, block_id, offset) = &locations[1];
+        assert_eq!(
+            Some(b"PebbleDB is an LSM-based storage engine".to_vec()),
+            log_manager.record_at(block_id, *offset).unwrap()
+        );
+        let (_, block_id, offset) = &locations[2];
+        assert_eq!(
+            Some(b"BoltDB is a B+Tree storage engine".to_vec()),
+            log_manager.record_at(block_id, *offset).unwrap()
+        );
+        assert_eq!(None, log_manager.record_at(block_id, *offset + 1).unwrap());
+    }
+
+    #[test]
+    fn flush_unsaved_records_when_the_log_manager_is_dropped() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        {
+            let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+            log_manager.append(b"RocksDB").unwrap();
+            log_manager.append(b"PebbleDB").unwrap();
+        }
+
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![b"RocksDB".to_vec(), b"PebbleDB".to_vec()], records);
+    }
+
+    #[test]
+    fn close_a_log_manager_with_unsaved_records() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        log_manager.append(b"RocksDB").unwrap();
+        assert!(log_manager.close().is_ok());
+
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        let records: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![b"RocksDB".to_vec()], records);
+    }
+
+    #[test]
+    fn iterate_forward_over_every_record_spread_across_many_blocks() {
+        const BLOCK_SIZE_IN_BYTES: usize = 128;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+
+        let records: Vec<Vec<u8>> = (1..=300)
+            .map(|record_id| format!("Record {}", record_id).into_bytes())
+            .collect();
+        records.iter().for_each(|record| {
+            log_manager.append(record).unwrap();
+        });
+        assert!(file_manager.number_of_blocks(file_name).unwrap() > 1);
+
+        let scanned: Vec<Vec<u8>> = log_manager
+            .forward_iterator()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records, scanned);
+    }
+
+    #[test]
+    fn continue_the_log_sequence_after_reopening_a_preallocated_log() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        {
+            let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+            log_manager.preallocate(5).unwrap();
+            log_manager.append(b"RocksDB").unwrap();
+            log_manager.append(b"PebbleDB").unwrap();
+            log_manager.append(b"BoltDB").unwrap();
+        }
+
+        let mut log_manager = LogManager::new(&file_manager, file_name.to_string()).unwrap();
+        assert_eq!(4, log_manager.append(b"LMDB").unwrap());
+
+        let records: Vec<Vec<u8>> = log_manager
+            .iterate_from(2)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![b"PebbleDB".to_vec(), b"BoltDB".to_vec(), b"LMDB".to_vec()],
+            records
         );
-        assert_eq!(None, iterator.next());
+        assert_eq!(6, file_manager.number_of_blocks(file_name).unwrap());
     }
 }
@@ -1,3 +1,5 @@
+pub(crate) mod compressor;
 mod iterator;
 pub(crate) mod log_manager;
 mod page;
+mod sequenced_record;
@@ -1,11 +1,15 @@
+use crate::checksum;
 use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::raw::read_bytes_at;
 use crate::encodex::{EncoderDecoder, EndOffset};
+use crate::error::Error;
 use crate::file::starting_offsets::StartingOffsets;
 use byteorder::ByteOrder;
-use std::borrow::Cow;
-use std::rc::Rc;
+use std::sync::Arc;
 
 const RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS: usize = size_of::<u16>();
+const RESERVED_SIZE_FOR_RECORD_CHECKSUM: usize = size_of::<u32>();
+const RESERVED_SIZE_FOR_PAGE_CHECKSUM: usize = size_of::<u32>();
 
 pub(crate) struct LogPage {
     buffer: Vec<u8>,
@@ -14,41 +18,109 @@ pub(crate) struct LogPage {
 }
 
 pub(crate) struct BackwardRecordIterator {
-    //TODO: revisit, maybe a reference, or an Arc
-    page: Rc<LogPage>,
+    page: Arc<LogPage>,
     current_offset_index: Option<usize>,
+    corrupted: bool,
 }
 
 impl BackwardRecordIterator {
-    pub(crate) fn new(page: Rc<LogPage>) -> Self {
-        let current_offset_index = page.starting_offsets.length() - 1;
+    pub(crate) fn new(page: Arc<LogPage>) -> Self {
+        let current_offset_index = page.starting_offsets.length().checked_sub(1);
         Self {
             page,
-            current_offset_index: Some(current_offset_index),
+            current_offset_index,
+            corrupted: false,
         }
     }
 
     pub(crate) fn record(&mut self) -> Option<&[u8]> {
-        self.current_offset_index.and_then(|offset_index| {
-            self.page
-                .starting_offsets
-                .offset_at(offset_index)
-                .map(|record_starting_offset| {
-                    let record = self.page.bytes_at(*record_starting_offset as usize);
-                    self.current_offset_index = offset_index.checked_sub(1);
-                    record
-                })
-        })
+        let offset_index = self.current_offset_index?;
+        let record_starting_offset = *self.page.starting_offsets.offset_at(offset_index)?;
+        match self.page.bytes_at(record_starting_offset as usize) {
+            Some(record) => {
+                self.current_offset_index = offset_index.checked_sub(1);
+                Some(record)
+            }
+            None => {
+                self.current_offset_index = None;
+                self.corrupted = true;
+                None
+            }
+        }
+    }
+
+    /// Returns the record that `record` would return next, without advancing. A corrupt record
+    /// peeks as `None` and is only flagged once `record` reaches it.
+    pub(crate) fn peek(&self) -> Option<&[u8]> {
+        self.page.record_at(self.current_offset_index?)
+    }
+
+    pub(crate) fn is_corrupted(&self) -> bool {
+        self.corrupted
+    }
+}
+
+pub(crate) struct ForwardRecordIterator {
+    page: Arc<LogPage>,
+    current_offset_index: usize,
+    corrupted: bool,
+}
+
+impl ForwardRecordIterator {
+    pub(crate) fn new(page: Arc<LogPage>) -> Self {
+        Self {
+            page,
+            current_offset_index: 0,
+            corrupted: false,
+        }
+    }
+
+    pub(crate) fn record(&mut self) -> Option<&[u8]> {
+        if self.corrupted {
+            return None;
+        }
+        let record_starting_offset = *self
+            .page
+            .starting_offsets
+            .offset_at(self.current_offset_index)?;
+        match self.page.bytes_at(record_starting_offset as usize) {
+            Some(record) => {
+                self.current_offset_index += 1;
+                Some(record)
+            }
+            None => {
+                self.corrupted = true;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn is_corrupted(&self) -> bool {
+        self.corrupted
+    }
+}
+
+impl Iterator for BackwardRecordIterator {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.record().map(Vec::from)
     }
 }
 
 impl crate::page::Page for LogPage {
-    fn decode_from(buffer: Vec<u8>) -> Self {
+    fn decode_from(buffer: Vec<u8>) -> Result<Self, Error> {
         if buffer.is_empty() {
-            panic!("buffer cannot be empty while decoding the log page");
+            return Err(Error::Decode(
+                "buffer cannot be empty while decoding the log page".to_string(),
+            ));
         }
         PageDecoder::decode_page(buffer)
     }
+
+    fn encode(&mut self) -> &[u8] {
+        self.encode()
+    }
 }
 
 impl LogPage {
@@ -60,20 +132,59 @@ impl LogPage {
         }
     }
 
+    /// Empties the page while keeping its buffer, leaving it as if it was just created.
+    pub(crate) fn reset(&mut self) {
+        self.buffer.fill(0);
+        self.starting_offsets = StartingOffsets::new();
+        self.current_write_offset = 0;
+    }
+
     pub(crate) fn add(&mut self, data: &[u8]) -> bool {
-        if !self.has_capacity_for(data) {
+        if !self.has_capacity_for(data)
+            || self
+                .starting_offsets
+                .add_offset(self.current_write_offset)
+                .is_err()
+        {
             return false;
         }
-        self.starting_offsets
-            .add_offset(self.current_write_offset as u32);
+
+        let mut record = Vec::with_capacity(data.len() + RESERVED_SIZE_FOR_RECORD_CHECKSUM);
+        record.extend_from_slice(data);
+        record.extend_from_slice(&checksum::crc32(data).to_le_bytes());
 
         let bytes_needed_for_encoding =
-            BytesEncoderDecoder.encode(data, &mut self.buffer, self.current_write_offset);
+            BytesEncoderDecoder.encode(&record, &mut self.buffer, self.current_write_offset);
 
         self.current_write_offset += bytes_needed_for_encoding;
         true
     }
 
+    pub(crate) fn is_empty(&self) -> bool {
+        self.number_of_records() == 0
+    }
+
+    pub(crate) fn number_of_records(&self) -> usize {
+        self.starting_offsets.length()
+    }
+
+    pub(crate) fn record_at(&self, index: usize) -> Option<&[u8]> {
+        let starting_offset = *self.starting_offsets.offset_at(index)?;
+        self.bytes_at(starting_offset as usize)
+    }
+
+    pub(crate) fn last_record(&self) -> Option<&[u8]> {
+        self.record_at(self.number_of_records().checked_sub(1)?)
+    }
+
+    pub(crate) fn last_record_offset(&self) -> Option<u32> {
+        self.starting_offsets.last_offset().copied()
+    }
+
+    pub(crate) fn record_starting_at(&self, offset: u32) -> Option<&[u8]> {
+        self.record_at(self.starting_offsets.index_of_offset(offset)?)
+    }
+
     pub(crate) fn encode(&mut self) -> &[u8] {
         if self.starting_offsets.length() == 0 {
             return &self.buffer;
@@ -81,42 +192,63 @@ impl LogPage {
         let mut page_encoder = PageEncoder {
             buffer: &mut self.buffer,
             starting_offsets: &self.starting_offsets,
+            current_write_offset: self.current_write_offset,
         };
         page_encoder.encode();
         &self.buffer
     }
 
-    fn backward_iterator(self: Rc<LogPage>) -> BackwardRecordIterator {
-        if self.starting_offsets.length() == 0 {
-            panic!("empty log page")
-        }
+    fn backward_iterator(self: Arc<LogPage>) -> BackwardRecordIterator {
         BackwardRecordIterator::new(self.clone())
     }
 
-    fn bytes_at(&self, offset: usize) -> &[u8] {
-        let (decoded, _) = BytesEncoderDecoder.decode(&self.buffer, offset);
-        match decoded {
-            Cow::Borrowed(slice) => slice,
-            _ => unreachable!(),
+    // A record that runs past the page or fails its checksum reads as `None`.
+    fn bytes_at(&self, offset: usize) -> Option<&[u8]> {
+        let record = read_bytes_at(&self.buffer, offset).ok()?;
+        if record.len() < RESERVED_SIZE_FOR_RECORD_CHECKSUM {
+            return None;
+        }
+        let (data, checksum) = record.split_at(record.len() - RESERVED_SIZE_FOR_RECORD_CHECKSUM);
+        if checksum::crc32(data) != byteorder::LittleEndian::read_u32(checksum) {
+            return None;
         }
+        Some(data)
     }
 
-    fn has_capacity_for(&self, buffer: &[u8]) -> bool {
-        let bytes_available = self.buffer.len()
-            - self.current_write_offset
-            - self.starting_offsets.size_in_bytes()
-            - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS;
+    pub(crate) fn fits_in_an_empty_page(block_size: usize, buffer: &[u8]) -> bool {
+        block_size
+            >= RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+                + RESERVED_SIZE_FOR_PAGE_CHECKSUM
+                + Self::bytes_needed_for(buffer)
+    }
 
-        let bytes_needed = BytesEncoderDecoder.bytes_needed_for_encoding(buffer)
-            + StartingOffsets::size_in_bytes_for_an_offset();
+    fn has_capacity_for(&self, buffer: &[u8]) -> bool {
+        if buffer.len() + RESERVED_SIZE_FOR_RECORD_CHECKSUM > BytesEncoderDecoder::MAX_LENGTH {
+            return false;
+        }
+        let bytes_available = self
+            .buffer
+            .len()
+            .checked_sub(self.current_write_offset)
+            .and_then(|bytes| bytes.checked_sub(self.starting_offsets.size_in_bytes()))
+            .and_then(|bytes| bytes.checked_sub(RESERVED_SIZE_FOR_PAGE_CHECKSUM))
+            .and_then(|bytes| bytes.checked_sub(RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS));
+
+        bytes_available
+            .is_some_and(|bytes_available| bytes_available >= Self::bytes_needed_for(buffer))
+    }
 
-        bytes_available >= bytes_needed
+    fn bytes_needed_for(buffer: &[u8]) -> usize {
+        BytesEncoderDecoder.bytes_needed_for_encoding(buffer)
+            + RESERVED_SIZE_FOR_RECORD_CHECKSUM
+            + StartingOffsets::size_in_bytes_for_an_offset()
     }
 }
 
 struct PageEncoder<'a> {
     buffer: &'a mut [u8],
     starting_offsets: &'a StartingOffsets,
+    current_write_offset: usize,
 }
 
 struct PageDecoder;
@@ -124,6 +256,7 @@ struct PageDecoder;
 impl PageEncoder<'_> {
     fn encode(&mut self) {
         self.write_encoded_starting_offsets(&self.starting_offsets.encode());
+        self.write_page_checksum();
         self.write_number_of_starting_offsets();
     }
 
@@ -131,6 +264,7 @@ impl PageEncoder<'_> {
         let encoded_page = &mut self.buffer;
         let offset_to_write_encoded_starting_offsets = encoded_page.len()
             - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            - RESERVED_SIZE_FOR_PAGE_CHECKSUM
             - self.starting_offsets.size_in_bytes();
 
         encoded_page[offset_to_write_encoded_starting_offsets
@@ -138,6 +272,18 @@ impl PageEncoder<'_> {
             .copy_from_slice(encoded_starting_offsets);
     }
 
+    fn write_page_checksum(&mut self) {
+        let page_checksum = checksum::crc32(&self.buffer[..self.current_write_offset]);
+        let offset_to_write_page_checksum = self.buffer.len()
+            - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            - RESERVED_SIZE_FOR_PAGE_CHECKSUM;
+
+        byteorder::LittleEndian::write_u32(
+            &mut self.buffer[offset_to_write_page_checksum..],
+            page_checksum,
+        );
+    }
+
     fn write_number_of_starting_offsets(&mut self) {
         let encoded_page = &mut self.buffer;
         let encoded_page_length = encoded_page.len();
@@ -150,28 +296,62 @@ impl PageEncoder<'_> {
 }
 
 impl PageDecoder {
-    pub(crate) fn decode_page(buffer: Vec<u8>) -> LogPage {
+    pub(crate) fn decode_page(buffer: Vec<u8>) -> Result<LogPage, Error> {
+        if buffer.len() < RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS {
+            return Err(Error::Decode(format!(
+                "log page of {} bytes is too small to hold the number of offsets",
+                buffer.len()
+            )));
+        }
         let offset_containing_number_of_offsets =
             buffer.len() - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS;
 
         let number_of_offsets =
             byteorder::LittleEndian::read_u16(&buffer[offset_containing_number_of_offsets..])
                 as usize;
+        if number_of_offsets == 0 {
+            if buffer[..offset_containing_number_of_offsets]
+                .iter()
+                .any(|byte| *byte != 0)
+            {
+                return Err(Error::Corrupt(
+                    "log page holds records but its number of offsets is zero".to_string(),
+                ));
+            }
+            return Ok(LogPage {
+                buffer,
+                starting_offsets: StartingOffsets::new(),
+                current_write_offset: 0,
+            });
+        }
+        if StartingOffsets::size_in_bytes_for(number_of_offsets) + RESERVED_SIZE_FOR_PAGE_CHECKSUM
+            > offset_containing_number_of_offsets
+        {
+            return Err(Error::Corrupt(format!(
+                "log page of {} bytes cannot hold {} offsets",
+                buffer.len(),
+                number_of_offsets
+            )));
+        }
 
         let starting_offsets = Self::decode_starting_offsets(&buffer, &number_of_offsets);
-        let end_offset = Self::current_write_offset(&buffer, &starting_offsets);
-
-        LogPage {
+        starting_offsets.validate(Self::offset_containing_encoded_starting_offsets(
+            &buffer,
+            &number_of_offsets,
+        ))?;
+        let end_offset = Self::current_write_offset(&buffer, &starting_offsets)?;
+        Self::verify_page_checksum(&buffer, end_offset)?;
+
+        Ok(LogPage {
             buffer,
             starting_offsets,
             current_write_offset: end_offset,
-        }
+        })
     }
 
     fn decode_starting_offsets(buffer: &[u8], number_of_offsets: &usize) -> StartingOffsets {
-        let offset_containing_encoded_starting_offsets = buffer.len()
-            - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
-            - StartingOffsets::size_in_bytes_for(*number_of_offsets);
+        let offset_containing_encoded_starting_offsets =
+            Self::offset_containing_encoded_starting_offsets(buffer, number_of_offsets);
 
         StartingOffsets::decode_from(
             &buffer[offset_containing_encoded_starting_offsets
@@ -180,18 +360,74 @@ impl PageDecoder {
         )
     }
 
-    fn current_write_offset(buffer: &[u8], starting_offsets: &StartingOffsets) -> EndOffset {
-        let last_starting_offset = starting_offsets.last_offset().unwrap();
-        let (_, end_offset) = BytesEncoderDecoder.decode(buffer, *last_starting_offset as usize);
-        end_offset
+    fn current_write_offset(
+        buffer: &[u8],
+        starting_offsets: &StartingOffsets,
+    ) -> Result<EndOffset, Error> {
+        let last_starting_offset = *starting_offsets.last_offset().unwrap() as usize;
+        let end_of_records =
+            Self::offset_containing_encoded_starting_offsets(buffer, &starting_offsets.length());
+        if last_starting_offset + BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE > end_of_records
+        {
+            return Err(Error::Corrupt(format!(
+                "record offset {} lies outside the records of the log page",
+                last_starting_offset
+            )));
+        }
+        let record_length = byteorder::LittleEndian::read_u16(&buffer[last_starting_offset..]);
+        let end_offset = last_starting_offset
+            + BytesEncoderDecoder::RESERVED_SIZE_FOR_BYTE_SLICE
+            + record_length as usize;
+        if end_offset > end_of_records {
+            return Err(Error::Corrupt(format!(
+                "record at offset {} overruns the records of the log page",
+                last_starting_offset
+            )));
+        }
+        Ok(end_offset)
+    }
+
+    fn verify_page_checksum(buffer: &[u8], end_offset: EndOffset) -> Result<(), Error> {
+        let offset_containing_page_checksum =
+            buffer.len() - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS - RESERVED_SIZE_FOR_PAGE_CHECKSUM;
+        let page_checksum =
+            byteorder::LittleEndian::read_u32(&buffer[offset_containing_page_checksum..]);
+        if checksum::crc32(&buffer[..end_offset]) != page_checksum {
+            return Err(Error::Corrupt(
+                "checksum mismatch for the records of the log page".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn offset_containing_encoded_starting_offsets(
+        buffer: &[u8],
+        number_of_offsets: &usize,
+    ) -> usize {
+        buffer.len()
+            - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            - RESERVED_SIZE_FOR_PAGE_CHECKSUM
+            - StartingOffsets::size_in_bytes_for(*number_of_offsets)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::log::page::LogPage;
+    use crate::error::Error;
+    use crate::log::page::{
+        BackwardRecordIterator, ForwardRecordIterator, LogPage,
+        RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS, RESERVED_SIZE_FOR_PAGE_CHECKSUM,
+    };
     use crate::page::Page;
-    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn attempt_to_add_a_record_longer_than_the_encodable_length() {
+        let mut page = LogPage::new(128 * 1024);
+        assert!(!page.add(&[b'r'; 70 * 1024]));
+        assert!(page.is_empty());
+    }
 
     #[test]
     fn attempt_to_add_a_record_to_a_page_with_insufficient_size() {
@@ -201,18 +437,97 @@ mod tests {
 
     #[test]
     fn attempt_to_add_a_couple_of_records_in_a_page_with_size_sufficient_for_only_one_record() {
-        let mut page = LogPage::new(60);
+        let mut page = LogPage::new(64);
         assert!(page.add(b"RocksDB is an LSM-based key/value storage engine"));
         assert!(!page.add(b"RocksDB is an LSM-based key/value storage engine"));
     }
 
     #[test]
     fn attempt_to_add_a_couple_of_records_successfully_in_a_page_with_just_enough_size() {
-        let mut page = LogPage::new(110);
+        let mut page = LogPage::new(122);
         assert!(page.add(b"RocksDB is an LSM-based key/value storage engine"));
         assert!(page.add(b"RocksDB is an LSM-based key/value storage engine"));
     }
 
+    #[test]
+    fn add_records_until_the_page_is_full() {
+        let mut page = LogPage::new(128);
+        let mut records_added = 0;
+        while page.add(b"Bolt") {
+            records_added += 1;
+            assert!(records_added < 128);
+        }
+
+        assert_eq!(8, records_added);
+        assert!(!page.add(b"Bolt"));
+        assert!(page.add(b""));
+        assert!(!page.add(b""));
+    }
+
+    #[test]
+    fn add_records_to_a_page_after_resetting_it() {
+        let mut page = LogPage::new(128);
+        while page.add(b"Bolt") {}
+        page.encode();
+
+        page.reset();
+        assert!(page.is_empty());
+        assert!(page.add(b"RocksDB"));
+        assert!(page.add(b"PebbleDB"));
+
+        let decoded = LogPage::decode_from(page.encode().to_vec()).unwrap();
+        let records: Vec<Vec<u8>> = Arc::new(decoded).backward_iterator().collect();
+        assert_eq!(vec![b"PebbleDB".to_vec(), b"RocksDB".to_vec()], records);
+
+        let mut new_page = LogPage::new(128);
+        new_page.add(b"RocksDB");
+        new_page.add(b"PebbleDB");
+        assert_eq!(new_page.encode(), page.encode());
+    }
+
+    #[test]
+    fn add_records_to_a_decoded_page_until_it_is_full() {
+        let mut page = LogPage::new(128);
+        (0..3).for_each(|_| assert!(page.add(b"Bolt")));
+
+        let mut decoded = LogPage::decode_from(page.encode().to_vec()).unwrap();
+        let mut records_added = 3;
+        while decoded.add(b"Bolt") {
+            records_added += 1;
+            assert!(records_added < 128);
+        }
+        assert_eq!(8, records_added);
+
+        let decoded = LogPage::decode_from(decoded.encode().to_vec()).unwrap();
+        assert_eq!(8, decoded.number_of_records());
+        (0..8).for_each(|index| assert_eq!(Some(&b"Bolt"[..]), decoded.record_at(index)));
+    }
+
+    #[test]
+    fn attempt_to_add_a_record_to_a_page_smaller_than_its_footer() {
+        let mut page = LogPage::new(1);
+        assert!(!page.add(b"Bolt"));
+    }
+
+    #[test]
+    fn record_fits_in_an_empty_page() {
+        assert!(LogPage::fits_in_an_empty_page(34, &[b'r'; 18]));
+        assert!(!LogPage::fits_in_an_empty_page(34, &[b'r'; 19]));
+
+        let mut page = LogPage::new(34);
+        assert!(page.add(&[b'r'; 18]));
+    }
+
+    #[test]
+    fn last_record_of_a_page() {
+        let mut page = LogPage::new(4096);
+        assert_eq!(None, page.last_record());
+
+        page.add(b"RocksDB");
+        page.add(b"PebbleDB");
+        assert_eq!(Some(&b"PebbleDB"[..]), page.last_record());
+    }
+
     #[test]
     fn attempt_to_create_a_log_with_no_records() {
         let mut page = LogPage::new(110);
@@ -221,10 +536,11 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn attempt_to_create_a_log_iterator_with_no_records() {
+    fn iterate_over_a_log_page_with_no_records() {
         let page = LogPage::new(4096);
-        Rc::new(page).backward_iterator();
+        let mut iterator = Arc::new(page).backward_iterator();
+        assert!(iterator.next().is_none());
+        assert!(!iterator.is_corrupted());
     }
 
     #[test]
@@ -233,7 +549,7 @@ mod tests {
         page.add(b"RocksDB is an LSM-based key/value storage engine");
 
         let _ = page.encode();
-        let mut iterator = Rc::new(page).backward_iterator();
+        let mut iterator = Arc::new(page).backward_iterator();
         assert_eq!(
             b"RocksDB is an LSM-based key/value storage engine",
             iterator.record().unwrap()
@@ -247,7 +563,7 @@ mod tests {
         page.add(b"PebbleDB is an LSM-based key/value storage engine");
 
         let _ = page.encode();
-        let mut iterator = Rc::new(page).backward_iterator();
+        let mut iterator = Arc::new(page).backward_iterator();
 
         assert_eq!(
             b"PebbleDB is an LSM-based key/value storage engine",
@@ -270,7 +586,7 @@ mod tests {
             });
 
         let _ = page.encode();
-        let mut iterator = Rc::new(page).backward_iterator();
+        let mut iterator = Arc::new(page).backward_iterator();
 
         (1..=100).rev().for_each(|record_id| {
             let record = format!("Record {}", record_id);
@@ -280,9 +596,206 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn collect_records_of_a_log_page_in_reverse_order() {
+        let mut page = LogPage::new(4096);
+        page.add(b"RocksDB is an LSM-based key/value storage engine");
+        page.add(b"PebbleDB is an LSM-based key/value storage engine");
+        page.add(b"BoltDB is a B+Tree storage engine");
+
+        let records: Vec<Vec<u8>> = Arc::new(page).backward_iterator().collect();
+        assert_eq!(
+            vec![
+                b"BoltDB is a B+Tree storage engine".to_vec(),
+                b"PebbleDB is an LSM-based key/value storage engine".to_vec(),
+                b"RocksDB is an LSM-based key/value storage engine".to_vec(),
+            ],
+            records
+        );
+    }
+
+    #[test]
+    fn peek_a_record_without_advancing_the_backward_iterator() {
+        let mut page = LogPage::new(4096);
+        page.add(b"RocksDB is an LSM-based key/value storage engine");
+        page.add(b"BoltDB is a B+Tree storage engine");
+
+        let mut iterator = BackwardRecordIterator::new(Arc::new(page));
+        assert_eq!(
+            Some(b"BoltDB is a B+Tree storage engine".as_slice()),
+            iterator.peek()
+        );
+        assert_eq!(iterator.peek(), iterator.peek());
+        assert_eq!(
+            Some(b"BoltDB is a B+Tree storage engine".to_vec()),
+            iterator.record().map(Vec::from)
+        );
+        assert_eq!(
+            Some(b"RocksDB is an LSM-based key/value storage engine".as_slice()),
+            iterator.peek()
+        );
+        assert_eq!(
+            Some(b"RocksDB is an LSM-based key/value storage engine".to_vec()),
+            iterator.record().map(Vec::from)
+        );
+        assert_eq!(None, iterator.peek());
+        assert_eq!(None, iterator.record());
+    }
+
+    #[test]
+    fn iterate_over_records_of_a_log_page_in_another_thread() {
+        let mut page = LogPage::new(4096);
+        page.add(b"RocksDB is an LSM-based key/value storage engine");
+        page.add(b"BoltDB is a B+Tree storage engine");
+
+        let page = Arc::new(page);
+        let records = thread::spawn(move || page.backward_iterator().collect::<Vec<Vec<u8>>>())
+            .join()
+            .unwrap();
+        assert_eq!(
+            vec![
+                b"BoltDB is a B+Tree storage engine".to_vec(),
+                b"RocksDB is an LSM-based key/value storage engine".to_vec(),
+            ],
+            records
+        );
+    }
+
+    #[test]
+    fn number_of_records_before_and_after_decoding_the_page() {
+        let mut page = LogPage::new(4096);
+        (1..=7).for_each(|record_id| {
+            assert!(page.add(format!("Record {}", record_id).as_bytes()));
+        });
+        assert_eq!(7, page.number_of_records());
+
+        let decoded_page = LogPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(7, decoded_page.number_of_records());
+    }
+
+    #[test]
+    fn get_records_by_index() {
+        let mut page = LogPage::new(4096);
+        (0..5).for_each(|record_id| {
+            assert!(page.add(format!("Record {}", record_id).as_bytes()));
+        });
+
+        assert_eq!(Some(b"Record 0".as_slice()), page.record_at(0));
+        assert_eq!(Some(b"Record 2".as_slice()), page.record_at(2));
+        assert_eq!(Some(b"Record 4".as_slice()), page.record_at(4));
+        assert_eq!(None, page.record_at(5));
+    }
+
+    #[test]
     fn attempt_to_decode_page_with_zero_records() {
-        LogPage::decode_from(vec![]);
+        assert!(matches!(
+            LogPage::decode_from(vec![]),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_page_with_more_offsets_than_it_can_hold() {
+        let mut buffer = vec![0; 64];
+        buffer[62..].copy_from_slice(&100u16.to_le_bytes());
+
+        assert!(matches!(
+            LogPage::decode_from(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_page_with_an_offset_outside_the_records() {
+        let mut page = LogPage::new(64);
+        page.add(b"RocksDB");
+        let mut buffer = page.encode().to_vec();
+        buffer[54..58].copy_from_slice(&56u32.to_le_bytes());
+
+        assert!(matches!(
+            LogPage::decode_from(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_page_with_descending_offsets() {
+        let mut page = LogPage::new(64);
+        page.add(b"RocksDB");
+        page.add(b"PebbleDB");
+        let mut buffer = page.encode().to_vec();
+        let offsets_size = page.starting_offsets.size_in_bytes();
+        let offset_containing_starting_offsets = 64
+            - RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            - RESERVED_SIZE_FOR_PAGE_CHECKSUM
+            - offsets_size;
+        buffer
+            [offset_containing_starting_offsets..offset_containing_starting_offsets + offsets_size]
+            .rotate_left(offsets_size / 2);
+
+        assert!(matches!(
+            LogPage::decode_from(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_page_with_a_zeroed_footer() {
+        let mut page = LogPage::new(64);
+        page.add(b"RocksDB");
+        page.add(b"PebbleDB");
+        let mut buffer = page.encode().to_vec();
+        let footer_size = RESERVED_SIZE_FOR_NUMBER_OF_OFFSETS
+            + RESERVED_SIZE_FOR_PAGE_CHECKSUM
+            + page.starting_offsets.size_in_bytes();
+        buffer[64 - footer_size..].fill(0);
+
+        assert!(matches!(
+            LogPage::decode_from(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_page_with_a_flipped_byte_in_the_records() {
+        let mut page = LogPage::new(64);
+        page.add(b"RocksDB");
+        page.add(b"PebbleDB");
+        let mut buffer = page.encode().to_vec();
+        buffer[3] ^= 0xFF;
+
+        assert!(matches!(
+            LogPage::decode_from(buffer),
+            Err(Error::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn stop_iteration_at_a_corrupted_record() {
+        let mut page = LogPage::new(4096);
+        page.add(b"RocksDB is an LSM-based key/value storage engine");
+        page.add(b"PebbleDB is an LSM-based key/value storage engine");
+        page.add(b"BoltDB is a B+Tree storage engine");
+
+        let corrupted_offset = *page.starting_offsets.offset_at(1).unwrap() as usize + 4;
+        page.buffer[corrupted_offset] ^= 0xFF;
+
+        let mut iterator = Arc::new(page).backward_iterator();
+        assert_eq!(
+            b"BoltDB is a B+Tree storage engine",
+            iterator.record().unwrap()
+        );
+        assert!(!iterator.is_corrupted());
+        assert_eq!(None, iterator.record());
+        assert!(iterator.is_corrupted());
+        assert_eq!(None, iterator.record());
+    }
+
+    #[test]
+    fn decode_page_with_no_records() {
+        let decoded_page = LogPage::decode_from(vec![0; 4096]).unwrap();
+        let mut iterator = BackwardRecordIterator::new(Arc::new(decoded_page));
+
+        assert_eq!(None, iterator.record());
     }
 
     #[test]
@@ -291,10 +804,10 @@ mod tests {
         page.add(b"PebbleDB is an LSM-based key/value storage engine");
 
         let buffer = page.encode();
-        let decoded_page = LogPage::decode_from(buffer.to_vec());
+        let decoded_page = LogPage::decode_from(buffer.to_vec()).unwrap();
 
         let _ = page.encode();
-        let mut iterator = Rc::new(decoded_page).backward_iterator();
+        let mut iterator = Arc::new(decoded_page).backward_iterator();
 
         assert_eq!(
             b"PebbleDB is an LSM-based key/value storage engine",
@@ -310,10 +823,10 @@ mod tests {
         page.add(b"RocksDB is an LSM-based key/value storage engine");
 
         let buffer = page.encode();
-        let decoded_page = LogPage::decode_from(buffer.to_vec());
+        let decoded_page = LogPage::decode_from(buffer.to_vec()).unwrap();
 
         let _ = page.encode();
-        let mut iterator = Rc::new(decoded_page).backward_iterator();
+        let mut iterator = Arc::new(decoded_page).backward_iterator();
 
         assert_eq!(
             b"RocksDB is an LSM-based key/value storage engine",
@@ -336,8 +849,8 @@ mod tests {
             });
 
         let buffer = page.encode();
-        let decoded_page = LogPage::decode_from(buffer.to_vec());
-        let mut iterator = Rc::new(decoded_page).backward_iterator();
+        let decoded_page = LogPage::decode_from(buffer.to_vec()).unwrap();
+        let mut iterator = Arc::new(decoded_page).backward_iterator();
 
         (1..=50).rev().for_each(|record_id| {
             let record = format!("Record {}", record_id);
@@ -345,4 +858,79 @@ mod tests {
         });
         assert_eq!(None, iterator.record());
     }
+
+    #[test]
+    fn iterate_forward_over_records_in_a_page() {
+        let mut page = LogPage::new(4096);
+        page.add(b"RocksDB is an LSM-based key/value storage engine");
+        page.add(b"PebbleDB is an LSM-based key/value storage engine");
+
+        let mut iterator = ForwardRecordIterator::new(Arc::new(page));
+        assert_eq!(
+            b"RocksDB is an LSM-based key/value storage engine",
+            iterator.record().unwrap()
+        );
+        assert_eq!(
+            b"PebbleDB is an LSM-based key/value storage engine",
+            iterator.record().unwrap()
+        );
+        assert_eq!(None, iterator.record());
+        assert!(!iterator.is_corrupted());
+    }
+
+    #[test]
+    fn add_a_single_empty_record_and_read_it_back_from_the_decoded_page() {
+        let mut page = LogPage::new(16);
+        assert!(page.add(b""));
+        assert!(!page.add(b""));
+
+        let decoded = LogPage::decode_from(page.encode().to_vec()).unwrap();
+        assert_eq!(1, decoded.number_of_records());
+
+        let mut iterator = ForwardRecordIterator::new(Arc::new(decoded));
+        assert_eq!(Some(&b""[..]), iterator.record());
+        assert_eq!(None, iterator.record());
+        assert!(!iterator.is_corrupted());
+    }
+
+    #[test]
+    fn add_empty_and_non_empty_records_and_read_them_back_from_the_decoded_page() {
+        let mut page = LogPage::new(4096);
+        assert!(page.add(b""));
+        assert!(page.add(b"RocksDB"));
+        assert!(page.add(b""));
+
+        let decoded = Arc::new(LogPage::decode_from(page.encode().to_vec()).unwrap());
+        let mut iterator = ForwardRecordIterator::new(decoded.clone());
+        assert_eq!(Some(&b""[..]), iterator.record());
+        assert_eq!(Some(&b"RocksDB"[..]), iterator.record());
+        assert_eq!(Some(&b""[..]), iterator.record());
+        assert_eq!(None, iterator.record());
+
+        let records: Vec<Vec<u8>> = BackwardRecordIterator::new(decoded).collect();
+        assert_eq!(
+            vec![b"".to_vec(), b"RocksDB".to_vec(), b"".to_vec()],
+            records
+        );
+    }
+
+    #[test]
+    fn stop_forward_iteration_at_a_corrupted_record() {
+        let mut page = LogPage::new(4096);
+        page.add(b"RocksDB is an LSM-based key/value storage engine");
+        page.add(b"PebbleDB is an LSM-based key/value storage engine");
+        page.add(b"BoltDB is a B+Tree storage engine");
+
+        let corrupted_offset = *page.starting_offsets.offset_at(1).unwrap() as usize + 4;
+        page.buffer[corrupted_offset] ^= 0xFF;
+
+        let mut iterator = ForwardRecordIterator::new(Arc::new(page));
+        assert_eq!(
+            b"RocksDB is an LSM-based key/value storage engine",
+            iterator.record().unwrap()
+        );
+        assert_eq!(None, iterator.record());
+        assert!(iterator.is_corrupted());
+        assert_eq!(None, iterator.record());
+    }
 }
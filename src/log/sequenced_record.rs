@@ -0,0 +1,51 @@
+use crate::error::Error;
+use byteorder::ByteOrder;
+
+const RESERVED_SIZE_FOR_LOG_SEQUENCE_NUMBER: usize = size_of::<u64>();
+
+pub(crate) fn encode_sequenced_record(log_sequence_number: usize, record: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(RESERVED_SIZE_FOR_LOG_SEQUENCE_NUMBER + record.len());
+    encoded.extend_from_slice(&(log_sequence_number as u64).to_le_bytes());
+    encoded.extend_from_slice(record);
+    encoded
+}
+
+pub(crate) fn decode_sequenced_record(encoded: &[u8]) -> Result<(usize, &[u8]), Error> {
+    let (log_sequence_number, record) = encoded
+        .split_at_checked(RESERVED_SIZE_FOR_LOG_SEQUENCE_NUMBER)
+        .ok_or_else(|| {
+            Error::Decode(format!(
+                "record of {} bytes is too short to hold a log sequence number",
+                encoded.len()
+            ))
+        })?;
+    Ok((
+        byteorder::LittleEndian::read_u64(log_sequence_number) as usize,
+        record,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::log::sequenced_record::{decode_sequenced_record, encode_sequenced_record};
+
+    #[test]
+    fn encode_decode_a_sequenced_record() {
+        let encoded = encode_sequenced_record(42, b"RocksDB");
+
+        assert_eq!(15, encoded.len());
+        assert_eq!(
+            (42, &b"RocksDB"[..]),
+            decode_sequenced_record(&encoded).unwrap()
+        );
+    }
+
+    #[test]
+    fn attempt_to_decode_a_record_shorter_than_a_log_sequence_number() {
+        assert!(matches!(
+            decode_sequenced_record(&[1, 2, 3]),
+            Err(Error::Decode(_))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::buffer::page::BufferPage;
+use crate::error::Error;
+use crate::file::file_manager::FileManager;
+use crate::record::Record;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub(crate) struct Memtable {
+    entries: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+    size_in_bytes: usize,
+}
+
+impl Memtable {
+    pub(crate) fn new() -> Self {
+        Memtable {
+            entries: BTreeMap::new(),
+            size_in_bytes: 0,
+        }
+    }
+
+    pub(crate) fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.insert(key, Some(value.to_vec()));
+    }
+
+    pub(crate) fn delete(&mut self, key: &[u8]) {
+        self.insert(key, None);
+    }
+
+    pub(crate) fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key)?.as_deref()
+    }
+
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn flush_to<PathType: AsRef<Path>>(
+        &self,
+        file_manager: &FileManager<PathType>,
+        file_name: &str,
+    ) -> Result<(), Error> {
+        let mut page = BufferPage::new(file_manager.block_size);
+        for (key, value) in &self.entries {
+            let record = match value {
+                Some(value) => Record::new(key, value),
+                None => Record::tombstone(key),
+            };
+            match record.add_to(&mut page) {
+                Err(Error::PageFull) if !page.is_empty() => {
+                    file_manager.append_block(file_name, page.encode())?;
+                    page = BufferPage::new(file_manager.block_size);
+                    record.add_to(&mut page)?;
+                }
+                result => result?,
+            }
+        }
+        if !page.is_empty() {
+            file_manager.append_block(file_name, page.encode())?;
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, key: &[u8], value: Option<Vec<u8>>) {
+        let value_size = value.as_ref().map_or(0, Vec::len);
+        match self.entries.insert(key.to_vec(), value) {
+            Some(previous_value) => {
+                self.size_in_bytes -= previous_value.as_ref().map_or(0, Vec::len)
+            }
+            None => self.size_in_bytes += key.len(),
+        }
+        self.size_in_bytes += value_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::page::BufferPage;
+    use crate::file::block_id::BlockId;
+    use crate::file::file_manager::FileManager;
+    use crate::memtable::Memtable;
+    use crate::record::Record;
+    use std::path::Path;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    fn records_in<PathType: AsRef<Path>>(
+        file_manager: &FileManager<PathType>,
+        file_name: &str,
+    ) -> Vec<Record> {
+        let mut records = Vec::new();
+        for block_number in 0..file_manager.number_of_blocks(file_name).unwrap() {
+            let page = file_manager
+                .read::<BufferPage>(&BlockId::new(file_name, block_number))
+                .unwrap();
+            records.extend(Record::read_all(&page).unwrap());
+        }
+        records
+    }
+
+    #[test]
+    fn put_and_get_a_few_keys() {
+        let mut memtable = Memtable::new();
+        assert!(memtable.is_empty());
+        memtable.put(b"RocksDB", b"LSM");
+        memtable.put(b"BoltDB", b"B+Tree");
+
+        assert_eq!(Some(b"LSM".as_slice()), memtable.get(b"RocksDB"));
+        assert_eq!(Some(b"B+Tree".as_slice()), memtable.get(b"BoltDB"));
+        assert_eq!(None, memtable.get(b"PebbleDB"));
+        assert_eq!(22, memtable.size_in_bytes());
+        assert!(!memtable.is_empty());
+    }
+
+    #[test]
+    fn overwrite_the_value_of_a_key() {
+        let mut memtable = Memtable::new();
+        memtable.put(b"RocksDB", b"B+Tree");
+        memtable.put(b"RocksDB", b"LSM");
+
+        assert_eq!(Some(b"LSM".as_slice()), memtable.get(b"RocksDB"));
+        assert_eq!(10, memtable.size_in_bytes());
+    }
+
+    #[test]
+    fn delete_a_key_and_flush_its_tombstone() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let mut memtable = Memtable::new();
+        memtable.put(b"RocksDB", b"LSM");
+        memtable.put(b"BoltDB", b"B+Tree");
+        memtable.delete(b"RocksDB");
+        assert_eq!(None, memtable.get(b"RocksDB"));
+        assert_eq!(19, memtable.size_in_bytes());
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        memtable.flush_to(&file_manager, file_name).unwrap();
+
+        let records = records_in(&file_manager, file_name);
+        assert_eq!(
+            vec![
+                Record::new(b"BoltDB", b"B+Tree"),
+                Record::tombstone(b"RocksDB"),
+            ],
+            records
+        );
+        assert!(!records[0].is_tombstone());
+        assert!(records[1].is_tombstone());
+    }
+
+    #[test]
+    fn flush_the_memtable_in_key_order() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let mut memtable = Memtable::new();
+        memtable.put(b"RocksDB", b"LSM");
+        memtable.put(b"BoltDB", b"B+Tree");
+        memtable.put(b"PebbleDB", b"LSM");
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        memtable.flush_to(&file_manager, file_name).unwrap();
+
+        assert_eq!(
+            vec![
+                Record::new(b"BoltDB", b"B+Tree"),
+                Record::new(b"PebbleDB", b"LSM"),
+                Record::new(b"RocksDB", b"LSM"),
+            ],
+            records_in(&file_manager, file_name)
+        );
+    }
+
+    #[test]
+    fn flush_the_memtable_across_a_few_blocks() {
+        const BLOCK_SIZE_IN_BYTES: usize = 64;
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let mut memtable = Memtable::new();
+        (0..10).for_each(|key| {
+            memtable.put(format!("key-{}", key).as_bytes(), b"value");
+        });
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        memtable.flush_to(&file_manager, file_name).unwrap();
+
+        assert!(file_manager.number_of_blocks(file_name).unwrap() > 1);
+        let records = records_in(&file_manager, file_name);
+        assert_eq!(10, records.len());
+        records.iter().enumerate().for_each(|(key, record)| {
+            assert_eq!(format!("key-{}", key).as_bytes(), record.key());
+            assert_eq!(b"value", record.value());
+        });
+    }
+}
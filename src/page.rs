@@ -1,3 +1,13 @@
-pub(crate) trait Page {
-    fn decode_from(buffer: Vec<u8>) -> Self;
+use crate::error::Error;
+
+pub(crate) trait Page: Sized {
+    fn decode_from(buffer: Vec<u8>) -> Result<Self, Error>;
+
+    /// Decodes the page and fails if any part of it is corrupt. Pages whose `decode_from`
+    /// already verifies the whole buffer keep this default.
+    fn decode_verified(buffer: Vec<u8>) -> Result<Self, Error> {
+        Self::decode_from(buffer)
+    }
+
+    fn encode(&mut self) -> &[u8];
 }
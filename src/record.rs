@@ -0,0 +1,252 @@
+use crate::buffer::page::BufferPage;
+use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::EncoderDecoder;
+use crate::error::Error;
+
+const RECORD_LAYOUT_VERSION: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RecordType {
+    Put = 0,
+    Tombstone = 1,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Record {
+    record_type: RecordType,
+    key: Vec<u8>,
+    value: Vec<u8>,
+}
+
+impl Record {
+    pub(crate) const NUMBER_OF_FIELDS: usize = 4;
+
+    pub(crate) fn new(key: &[u8], value: &[u8]) -> Self {
+        Record {
+            record_type: RecordType::Put,
+            key: key.to_vec(),
+            value: value.to_vec(),
+        }
+    }
+
+    pub(crate) fn tombstone(key: &[u8]) -> Self {
+        Record {
+            record_type: RecordType::Tombstone,
+            key: key.to_vec(),
+            value: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_tombstone(&self) -> bool {
+        self.record_type == RecordType::Tombstone
+    }
+
+    pub(crate) fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub(crate) fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    pub(crate) fn into_key_value(self) -> (Vec<u8>, Vec<u8>) {
+        (self.key, self.value)
+    }
+
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        2 * size_of::<u8>()
+            + BytesEncoderDecoder.bytes_needed_for_encoding(&self.key)
+            + BytesEncoderDecoder.bytes_needed_for_encoding(&self.value)
+    }
+
+    pub(crate) fn add_to(&self, page: &mut BufferPage) -> Result<(), Error> {
+        let length = self.key.len().max(self.value.len());
+        if length > BytesEncoderDecoder::MAX_LENGTH {
+            return Err(Error::ValueTooLarge {
+                length,
+                maximum: BytesEncoderDecoder::MAX_LENGTH,
+            });
+        }
+        if self.size_in_bytes() > page.free_space_for_fields(Self::NUMBER_OF_FIELDS) {
+            return Err(Error::PageFull);
+        }
+        page.add_u8(RECORD_LAYOUT_VERSION)?;
+        page.add_u8(self.record_type as u8)?;
+        page.add_bytes(self.key.clone())?;
+        page.add_bytes(self.value.clone())
+    }
+
+    pub(crate) fn read_from(page: &BufferPage, index: usize) -> Result<Option<Record>, Error> {
+        let Some(version) = page.get_u8(index)? else {
+            return Ok(None);
+        };
+        if version != RECORD_LAYOUT_VERSION {
+            return Err(Error::Corrupt(format!(
+                "unsupported record layout version {} at field {}",
+                version, index
+            )));
+        }
+        let (Some(record_type), Some(key), Some(value)) = (
+            page.get_u8(index + 1)?,
+            page.get_bytes(index + 2)?,
+            page.get_bytes(index + 3)?,
+        ) else {
+            return Err(Error::Corrupt(format!(
+                "record at field {} is missing its type, key or value",
+                index
+            )));
+        };
+        let record_type = RecordType::try_from(record_type)?;
+        Ok(Some(Record {
+            record_type,
+            key: key.to_vec(),
+            value: value.to_vec(),
+        }))
+    }
+
+    pub(crate) fn read_all(page: &BufferPage) -> Result<Vec<Record>, Error> {
+        let mut records = Vec::new();
+        while let Some(record) = Self::read_from(page, records.len() * Self::NUMBER_OF_FIELDS)? {
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+impl TryFrom<u8> for RecordType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(RecordType::Put),
+            1 => Ok(RecordType::Tombstone),
+            _ => Err(Error::Corrupt(format!("unknown record type {}", value))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::page::BufferPage;
+    use crate::error::Error;
+    use crate::page::Page;
+    use crate::record::Record;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    fn encode_and_decode(page: &mut BufferPage) -> BufferPage {
+        BufferPage::decode_from(page.encode().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn add_a_record_and_read_it_back() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        Record::new(b"RocksDB", b"LSM-based storage engine")
+            .add_to(&mut page)
+            .unwrap();
+
+        let page = encode_and_decode(&mut page);
+        let record = Record::read_from(&page, 0).unwrap().unwrap();
+        assert_eq!(b"RocksDB", record.key());
+        assert_eq!(b"LSM-based storage engine", record.value());
+    }
+
+    #[test]
+    fn add_a_record_with_an_empty_key() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        Record::new(b"", b"LSM-based storage engine")
+            .add_to(&mut page)
+            .unwrap();
+
+        let page = encode_and_decode(&mut page);
+        let record = Record::read_from(&page, 0).unwrap().unwrap();
+        assert!(record.key().is_empty());
+        assert_eq!(b"LSM-based storage engine", record.value());
+    }
+
+    #[test]
+    fn add_a_record_with_an_empty_value() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        Record::new(b"RocksDB", b"").add_to(&mut page).unwrap();
+
+        let page = encode_and_decode(&mut page);
+        let record = Record::read_from(&page, 0).unwrap().unwrap();
+        assert_eq!(b"RocksDB", record.key());
+        assert!(record.value().is_empty());
+    }
+
+    #[test]
+    fn add_a_few_records_and_read_them_back() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        Record::new(b"BoltDB", b"B+Tree").add_to(&mut page).unwrap();
+        Record::new(b"RocksDB", b"LSM").add_to(&mut page).unwrap();
+
+        let page = encode_and_decode(&mut page);
+        assert_eq!(
+            Some(Record::new(b"RocksDB", b"LSM")),
+            Record::read_from(&page, Record::NUMBER_OF_FIELDS).unwrap()
+        );
+        assert_eq!(
+            None,
+            Record::read_from(&page, 2 * Record::NUMBER_OF_FIELDS).unwrap()
+        );
+    }
+
+    #[test]
+    fn add_a_tombstone_and_read_it_back() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        Record::new(b"BoltDB", b"B+Tree").add_to(&mut page).unwrap();
+        Record::tombstone(b"RocksDB").add_to(&mut page).unwrap();
+
+        let page = encode_and_decode(&mut page);
+        let record = Record::read_from(&page, 0).unwrap().unwrap();
+        assert!(!record.is_tombstone());
+
+        let record = Record::read_from(&page, Record::NUMBER_OF_FIELDS)
+            .unwrap()
+            .unwrap();
+        assert!(record.is_tombstone());
+        assert_eq!(b"RocksDB", record.key());
+        assert!(record.value().is_empty());
+    }
+
+    #[test]
+    fn read_all_the_records_of_a_page() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        Record::new(b"BoltDB", b"B+Tree").add_to(&mut page).unwrap();
+        Record::tombstone(b"RocksDB").add_to(&mut page).unwrap();
+
+        let page = encode_and_decode(&mut page);
+        assert_eq!(
+            vec![
+                Record::new(b"BoltDB", b"B+Tree"),
+                Record::tombstone(b"RocksDB")
+            ],
+            Record::read_all(&page).unwrap()
+        );
+    }
+
+    #[test]
+    fn attempt_to_add_a_record_to_a_full_page() {
+        let mut page = BufferPage::new(64);
+        assert!(matches!(
+            Record::new(b"RocksDB", &[b'v'; 64]).add_to(&mut page),
+            Err(Error::PageFull)
+        ));
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn attempt_to_read_a_record_with_an_unsupported_layout_version() {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u8(9).unwrap();
+        page.add_u8(0).unwrap();
+        page.add_bytes(b"RocksDB".to_vec()).unwrap();
+        page.add_bytes(b"LSM".to_vec()).unwrap();
+
+        assert!(matches!(
+            Record::read_from(&page, 0),
+            Err(Error::Corrupt(_))
+        ));
+    }
+}
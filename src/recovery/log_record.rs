@@ -0,0 +1,279 @@
+use crate::encodex::bytes_encoder_decoder::BytesEncoderDecoder;
+use crate::encodex::raw::{read_bytes_at, read_str_at, read_u16_at, read_u32_at, read_u8_at};
+use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::{EncoderDecoder, U16EncoderDecoder, U32EncoderDecoder, U8EncoderDecoder};
+use crate::error::Error;
+use crate::file::block_id::BlockId;
+
+pub(crate) type TransactionNumber = u32;
+
+const START: u8 = 0;
+const COMMIT: u8 = 1;
+const ROLLBACK: u8 = 2;
+const SET: u8 = 3;
+const CHECKPOINT: u8 = 4;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum LogRecord {
+    Start(TransactionNumber),
+    Commit(TransactionNumber),
+    Rollback(TransactionNumber),
+    Set(SetRecord),
+    Checkpoint(Vec<TransactionNumber>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SetRecord {
+    pub(crate) transaction_number: TransactionNumber,
+    pub(crate) block_id: BlockId,
+    pub(crate) field_index: u16,
+    pub(crate) before_image: Vec<u8>,
+    pub(crate) after_image: Vec<u8>,
+}
+
+impl LogRecord {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        match self {
+            LogRecord::Start(transaction_number) => {
+                Self::encode_transaction_record(START, *transaction_number)
+            }
+            LogRecord::Commit(transaction_number) => {
+                Self::encode_transaction_record(COMMIT, *transaction_number)
+            }
+            LogRecord::Rollback(transaction_number) => {
+                Self::encode_transaction_record(ROLLBACK, *transaction_number)
+            }
+            LogRecord::Set(set_record) => set_record.encode(),
+            LogRecord::Checkpoint(active_transactions) => {
+                Self::encode_checkpoint_record(active_transactions)
+            }
+        }
+    }
+
+    pub(crate) fn decode(buffer: &[u8]) -> Result<LogRecord, Error> {
+        let record_type = read_u8_at(buffer, 0)?;
+        let offset = size_of::<u8>();
+        let record = match record_type {
+            START => LogRecord::Start(read_u32_at(buffer, offset)?),
+            COMMIT => LogRecord::Commit(read_u32_at(buffer, offset)?),
+            ROLLBACK => LogRecord::Rollback(read_u32_at(buffer, offset)?),
+            SET => LogRecord::Set(SetRecord::decode(buffer, offset)?),
+            CHECKPOINT => LogRecord::Checkpoint(Self::decode_checkpoint_record(buffer, offset)?),
+            _ => {
+                return Err(Error::Decode(format!(
+                    "unknown log record type {}",
+                    record_type
+                )))
+            }
+        };
+        Ok(record)
+    }
+
+    pub(crate) fn transaction_number(&self) -> Option<TransactionNumber> {
+        match self {
+            LogRecord::Start(transaction_number)
+            | LogRecord::Commit(transaction_number)
+            | LogRecord::Rollback(transaction_number) => Some(*transaction_number),
+            LogRecord::Set(set_record) => Some(set_record.transaction_number),
+            LogRecord::Checkpoint(_) => None,
+        }
+    }
+
+    fn encode_transaction_record(
+        record_type: u8,
+        transaction_number: TransactionNumber,
+    ) -> Vec<u8> {
+        let mut buffer = vec![
+            0;
+            U8EncoderDecoder.bytes_needed_for_encoding(&record_type)
+                + U32EncoderDecoder.bytes_needed_for_encoding(&transaction_number)
+        ];
+        let offset = U8EncoderDecoder.encode(&record_type, &mut buffer, 0);
+        U32EncoderDecoder.encode(&transaction_number, &mut buffer, offset);
+        buffer
+    }
+
+    fn encode_checkpoint_record(active_transactions: &[TransactionNumber]) -> Vec<u8> {
+        let number_of_transactions = active_transactions.len() as u32;
+        let mut buffer = vec![
+            0;
+            U8EncoderDecoder.bytes_needed_for_encoding(&CHECKPOINT)
+                + U32EncoderDecoder
+                    .bytes_needed_for_encoding(&number_of_transactions)
+                + active_transactions
+                    .iter()
+                    .map(|transaction_number| {
+                        U32EncoderDecoder.bytes_needed_for_encoding(transaction_number)
+                    })
+                    .sum::<usize>()
+        ];
+        let mut offset = U8EncoderDecoder.encode(&CHECKPOINT, &mut buffer, 0);
+        offset += U32EncoderDecoder.encode(&number_of_transactions, &mut buffer, offset);
+        for transaction_number in active_transactions {
+            offset += U32EncoderDecoder.encode(transaction_number, &mut buffer, offset);
+        }
+        buffer
+    }
+
+    // The number of transactions comes from disk, so it only bounds the reads, which stop at
+    // the first one beyond the buffer, rather than sizing an allocation.
+    fn decode_checkpoint_record(
+        buffer: &[u8],
+        from_offset: usize,
+    ) -> Result<Vec<TransactionNumber>, Error> {
+        let number_of_transactions = read_u32_at(buffer, from_offset)? as usize;
+        let offset = from_offset + size_of::<u32>();
+        (0..number_of_transactions)
+            .map(|index| read_u32_at(buffer, offset + index * size_of::<u32>()))
+            .collect()
+    }
+}
+
+impl SetRecord {
+    fn encode(&self) -> Vec<u8> {
+        let block_number = self.block_id.block_number as u32;
+        let mut buffer =
+            vec![
+                0;
+                U8EncoderDecoder.bytes_needed_for_encoding(&SET)
+                    + U32EncoderDecoder.bytes_needed_for_encoding(&self.transaction_number)
+                    + StrEncoderDecoder.bytes_needed_for_encoding(self.block_id.file_name())
+                    + U32EncoderDecoder.bytes_needed_for_encoding(&block_number)
+                    + U16EncoderDecoder.bytes_needed_for_encoding(&self.field_index)
+                    + BytesEncoderDecoder.bytes_needed_for_encoding(&self.before_image)
+                    + BytesEncoderDecoder.bytes_needed_for_encoding(&self.after_image)
+            ];
+
+        let mut offset = U8EncoderDecoder.encode(&SET, &mut buffer, 0);
+        offset += U32EncoderDecoder.encode(&self.transaction_number, &mut buffer, offset);
+        offset += StrEncoderDecoder.encode(self.block_id.file_name(), &mut buffer, offset);
+        offset += U32EncoderDecoder.encode(&block_number, &mut buffer, offset);
+        offset += U16EncoderDecoder.encode(&self.field_index, &mut buffer, offset);
+        offset += BytesEncoderDecoder.encode(&self.before_image, &mut buffer, offset);
+        BytesEncoderDecoder.encode(&self.after_image, &mut buffer, offset);
+        buffer
+    }
+
+    fn decode(buffer: &[u8], from_offset: usize) -> Result<SetRecord, Error> {
+        let transaction_number = read_u32_at(buffer, from_offset)?;
+        let mut offset = from_offset + size_of::<u32>();
+        let file_name = read_str_at(buffer, offset)?;
+        offset += StrEncoderDecoder.bytes_needed_for_encoding(file_name);
+        let block_number = read_u32_at(buffer, offset)?;
+        offset += size_of::<u32>();
+        let field_index = read_u16_at(buffer, offset)?;
+        offset += size_of::<u16>();
+        let before_image = read_bytes_at(buffer, offset)?;
+        offset += BytesEncoderDecoder.bytes_needed_for_encoding(before_image);
+        let after_image = read_bytes_at(buffer, offset)?;
+
+        Ok(SetRecord {
+            transaction_number,
+            block_id: BlockId::new(file_name, block_number as usize),
+            field_index,
+            before_image: before_image.to_vec(),
+            after_image: after_image.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::file::block_id::BlockId;
+    use crate::recovery::log_record::{LogRecord, SetRecord};
+
+    #[test]
+    fn encode_decode_start_record() {
+        let record = LogRecord::Start(10);
+        assert_eq!(
+            LogRecord::Start(10),
+            LogRecord::decode(&record.encode()).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_decode_commit_record() {
+        let record = LogRecord::Commit(10);
+        assert_eq!(
+            LogRecord::Commit(10),
+            LogRecord::decode(&record.encode()).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_decode_rollback_record() {
+        let record = LogRecord::Rollback(10);
+        assert_eq!(
+            LogRecord::Rollback(10),
+            LogRecord::decode(&record.encode()).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_decode_set_record() {
+        let record = LogRecord::Set(SetRecord {
+            transaction_number: 10,
+            block_id: BlockId::new("btree.db", 3),
+            field_index: 2,
+            before_image: b"Bolt-DB".to_vec(),
+            after_image: b"RocksDB".to_vec(),
+        });
+
+        let decoded = LogRecord::decode(&record.encode()).unwrap();
+        assert_eq!(record, decoded);
+        assert_eq!(Some(10), decoded.transaction_number());
+    }
+
+    #[test]
+    fn encode_decode_checkpoint_record() {
+        let record = LogRecord::Checkpoint(vec![10, 20, 30]);
+
+        let decoded = LogRecord::decode(&record.encode()).unwrap();
+        assert_eq!(record, decoded);
+        assert_eq!(None, decoded.transaction_number());
+    }
+
+    #[test]
+    fn encode_decode_checkpoint_record_with_no_active_transactions() {
+        let record = LogRecord::Checkpoint(Vec::new());
+        assert_eq!(record, LogRecord::decode(&record.encode()).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_decode_a_record_of_an_unknown_type() {
+        assert!(matches!(
+            LogRecord::decode(&[9, 0, 0, 0, 10]),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn attempt_to_decode_an_empty_record() {
+        assert!(matches!(LogRecord::decode(&[]), Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn attempt_to_decode_a_truncated_record() {
+        assert!(matches!(LogRecord::decode(&[0, 1]), Err(Error::Decode(_))));
+
+        let encoded = LogRecord::Set(SetRecord {
+            transaction_number: 10,
+            block_id: BlockId::new("test.db", 1),
+            field_index: 2,
+            before_image: vec![1, 2, 3],
+            after_image: vec![4, 5, 6],
+        })
+        .encode();
+        assert!((1..encoded.len())
+            .all(|length| matches!(LogRecord::decode(&encoded[..length]), Err(Error::Decode(_)))));
+    }
+
+    #[test]
+    fn attempt_to_decode_a_checkpoint_record_with_more_transactions_than_bytes() {
+        assert!(matches!(
+            LogRecord::decode(&[4, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 10]),
+            Err(Error::Decode(_))
+        ));
+    }
+}
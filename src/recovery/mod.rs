@@ -0,0 +1,2 @@
+pub(crate) mod log_record;
+pub(crate) mod recovery_manager;
@@ -0,0 +1,401 @@
+use crate::buffer::buffer_manager::BufferManager;
+use crate::error::Error;
+use crate::file::block_id::BlockId;
+use crate::recovery::log_record::{LogRecord, SetRecord, TransactionNumber};
+use crate::transaction::start_transaction_numbers_after;
+use std::collections::HashSet;
+use std::path::Path;
+
+pub(crate) struct RecoveryManager<'b, 'l, 'a, PathType: AsRef<Path>> {
+    buffer_manager: &'b BufferManager<'l, 'a, PathType>,
+}
+
+impl<'b, 'l, 'a, PathType: AsRef<Path>> RecoveryManager<'b, 'l, 'a, PathType> {
+    pub(crate) fn new(
+        buffer_manager: &'b BufferManager<'l, 'a, PathType>,
+    ) -> RecoveryManager<'b, 'l, 'a, PathType> {
+        RecoveryManager { buffer_manager }
+    }
+
+    pub(crate) fn checkpoint(
+        &mut self,
+        active_transactions: &[TransactionNumber],
+    ) -> Result<usize, Error> {
+        self.buffer_manager.flush_all()?;
+        let mut log_manager = self.buffer_manager.log_manager();
+        let log_sequence_number =
+            log_manager.append(&LogRecord::Checkpoint(active_transactions.to_vec()).encode())?;
+        log_manager.flush(log_sequence_number)?;
+        Ok(log_sequence_number)
+    }
+
+    pub(crate) fn recover(&mut self) -> Result<(), Error> {
+        if let Some(transaction_number) = self.highest_transaction_number()? {
+            start_transaction_numbers_after(transaction_number);
+        }
+        let mut finished_transactions = HashSet::new();
+        let mut committed_transactions = HashSet::new();
+        let mut set_records_to_redo = Vec::new();
+        let mut transactions_to_undo_before_checkpoint: Option<HashSet<TransactionNumber>> = None;
+
+        let iterator = self.buffer_manager.log_manager().backward_iterator()?;
+        for record in iterator {
+            match LogRecord::decode(&record?)? {
+                LogRecord::Commit(transaction_number) => {
+                    finished_transactions.insert(transaction_number);
+                    committed_transactions.insert(transaction_number);
+                }
+                LogRecord::Rollback(transaction_number) => {
+                    finished_transactions.insert(transaction_number);
+                }
+                LogRecord::Set(set_record) => match &transactions_to_undo_before_checkpoint {
+                    None if finished_transactions.contains(&set_record.transaction_number) => {
+                        set_records_to_redo.push(set_record)
+                    }
+                    None => self.undo(&set_record)?,
+                    Some(transactions_to_undo)
+                        if transactions_to_undo.contains(&set_record.transaction_number) =>
+                    {
+                        self.undo(&set_record)?
+                    }
+                    Some(_) => {}
+                },
+                LogRecord::Checkpoint(active_transactions) => {
+                    if transactions_to_undo_before_checkpoint.is_none() {
+                        let transactions_to_undo: HashSet<TransactionNumber> = active_transactions
+                            .into_iter()
+                            .filter(|transaction_number| {
+                                !finished_transactions.contains(transaction_number)
+                            })
+                            .collect();
+                        if transactions_to_undo.is_empty() {
+                            break;
+                        }
+                        transactions_to_undo_before_checkpoint = Some(transactions_to_undo);
+                    }
+                }
+                LogRecord::Start(transaction_number) => {
+                    if let Some(transactions_to_undo) = &mut transactions_to_undo_before_checkpoint
+                    {
+                        transactions_to_undo.remove(&transaction_number);
+                        if transactions_to_undo.is_empty() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        for set_record in set_records_to_redo.iter().rev() {
+            if committed_transactions.contains(&set_record.transaction_number) {
+                self.redo(set_record)?;
+            }
+        }
+        self.buffer_manager.flush_all()?;
+        Ok(())
+    }
+
+    pub(crate) fn rollback(&mut self, transaction_number: TransactionNumber) -> Result<(), Error> {
+        let iterator = self.buffer_manager.log_manager().backward_iterator()?;
+        for record in iterator {
+            match LogRecord::decode(&record?)? {
+                LogRecord::Start(started) if started == transaction_number => break,
+                LogRecord::Set(set_record)
+                    if set_record.transaction_number == transaction_number =>
+                {
+                    self.undo(&set_record)?
+                }
+                _ => {}
+            }
+        }
+        self.buffer_manager.flush_all()?;
+        Ok(())
+    }
+
+    // Undo stops at the latest checkpoint, so this reads the whole log instead. Concurrent
+    // transactions may append their start records out of order, so the latest one is not
+    // necessarily the highest.
+    fn highest_transaction_number(&self) -> Result<Option<TransactionNumber>, Error> {
+        let mut highest = None;
+        let iterator = self.buffer_manager.log_manager().backward_iterator()?;
+        for record in iterator {
+            let transaction_number = match LogRecord::decode(&record?)? {
+                LogRecord::Checkpoint(active_transactions) => active_transactions.into_iter().max(),
+                record => record.transaction_number(),
+            };
+            highest = highest.max(transaction_number);
+        }
+        Ok(highest)
+    }
+
+    fn undo(&mut self, set_record: &SetRecord) -> Result<(), Error> {
+        self.apply_image(
+            set_record.transaction_number,
+            &set_record.block_id,
+            set_record.field_index,
+            &set_record.before_image,
+        )
+    }
+
+    fn redo(&mut self, set_record: &SetRecord) -> Result<(), Error> {
+        self.apply_image(
+            set_record.transaction_number,
+            &set_record.block_id,
+            set_record.field_index,
+            &set_record.after_image,
+        )
+    }
+
+    fn apply_image(
+        &mut self,
+        transaction_number: TransactionNumber,
+        block_id: &BlockId,
+        field_index: u16,
+        image: &[u8],
+    ) -> Result<(), Error> {
+        let pinned = self.buffer_manager.pin(block_id.clone())?;
+        let mut buffer = pinned.write();
+        if let Some(page) = buffer.page() {
+            page.overwrite_encoded_field(image, field_index as usize)?;
+        }
+        buffer.set_modified(transaction_number as isize, 0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::buffer_manager::BufferManager;
+    use crate::buffer::page::BufferPage;
+    use crate::file::block_id::BlockId;
+    use crate::file::file_manager::FileManager;
+    use crate::log::log_manager::LogManager;
+    use crate::recovery::log_record::{LogRecord, SetRecord};
+    use crate::recovery::recovery_manager::RecoveryManager;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    fn encoded_u16(value: u16) -> Vec<u8> {
+        value.to_le_bytes().to_vec()
+    }
+
+    fn set_record(transaction_number: u32, block_id: &BlockId, before: u16, after: u16) -> Vec<u8> {
+        LogRecord::Set(SetRecord {
+            transaction_number,
+            block_id: block_id.clone(),
+            field_index: 0,
+            before_image: encoded_u16(before),
+            after_image: encoded_u16(after),
+        })
+        .encode()
+    }
+
+    fn write_page_with_u16(
+        file_manager: &FileManager<&std::path::Path>,
+        block_id: &BlockId,
+        value: u16,
+    ) {
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u16(value).unwrap();
+        file_manager.write(block_id, page.encode()).unwrap();
+    }
+
+    #[test]
+    fn recover_redoes_a_committed_transaction() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+        write_page_with_u16(&file_manager, &block_id, 100);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager.append(&LogRecord::Start(1).encode()).unwrap();
+        log_manager
+            .append(&set_record(1, &block_id, 100, 200))
+            .unwrap();
+        log_manager.append(&LogRecord::Commit(1).encode()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(200), page.get_u16(0).unwrap());
+    }
+
+    #[test]
+    fn recover_undoes_an_uncommitted_transaction() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager.append(&LogRecord::Start(1).encode()).unwrap();
+        log_manager
+            .append(&set_record(1, &block_id, 100, 200))
+            .unwrap();
+        write_page_with_u16(&file_manager, &block_id, 200);
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(100), page.get_u16(0).unwrap());
+    }
+
+    #[test]
+    fn recover_a_committed_and_an_uncommitted_transaction() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let committed_block_id = BlockId::new(data_file_name, 0);
+        let uncommitted_block_id = BlockId::new(data_file_name, 1);
+        write_page_with_u16(&file_manager, &committed_block_id, 100);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager.append(&LogRecord::Start(1).encode()).unwrap();
+        log_manager.append(&LogRecord::Start(2).encode()).unwrap();
+        log_manager
+            .append(&set_record(1, &committed_block_id, 100, 200))
+            .unwrap();
+        log_manager
+            .append(&set_record(2, &uncommitted_block_id, 300, 400))
+            .unwrap();
+        let log_sequence_number = log_manager.append(&LogRecord::Commit(1).encode()).unwrap();
+        log_manager.flush(log_sequence_number).unwrap();
+        write_page_with_u16(&file_manager, &uncommitted_block_id, 400);
+        drop(log_manager);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let page = file_manager
+            .read::<BufferPage>(&committed_block_id)
+            .unwrap();
+        assert_eq!(Some(200), page.get_u16(0).unwrap());
+
+        let page = file_manager
+            .read::<BufferPage>(&uncommitted_block_id)
+            .unwrap();
+        assert_eq!(Some(300), page.get_u16(0).unwrap());
+    }
+
+    #[test]
+    fn rollback_a_transaction() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+        let other_block_id = BlockId::new(data_file_name, 1);
+        write_page_with_u16(&file_manager, &block_id, 200);
+        write_page_with_u16(&file_manager, &other_block_id, 400);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager.append(&LogRecord::Start(1).encode()).unwrap();
+        log_manager.append(&LogRecord::Start(2).encode()).unwrap();
+        log_manager
+            .append(&set_record(1, &block_id, 100, 200))
+            .unwrap();
+        log_manager
+            .append(&set_record(2, &other_block_id, 300, 400))
+            .unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        RecoveryManager::new(&buffer_manager).rollback(1).unwrap();
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(100), page.get_u16(0).unwrap());
+
+        let page = file_manager.read::<BufferPage>(&other_block_id).unwrap();
+        assert_eq!(Some(400), page.get_u16(0).unwrap());
+    }
+
+    #[test]
+    fn recover_replays_only_the_records_after_the_latest_checkpoint() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let checkpointed_block_id = BlockId::new(data_file_name, 0);
+        let block_id = BlockId::new(data_file_name, 1);
+        write_page_with_u16(&file_manager, &checkpointed_block_id, 100);
+        write_page_with_u16(&file_manager, &block_id, 300);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager.append(&LogRecord::Start(1).encode()).unwrap();
+        log_manager
+            .append(&set_record(1, &checkpointed_block_id, 100, 200))
+            .unwrap();
+        log_manager.append(&LogRecord::Commit(1).encode()).unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        let checkpoint_log_sequence_number = RecoveryManager::new(&buffer_manager)
+            .checkpoint(&[])
+            .unwrap();
+        assert_eq!(4, checkpoint_log_sequence_number);
+
+        {
+            let mut log_manager = buffer_manager.log_manager();
+            log_manager.append(&LogRecord::Start(2).encode()).unwrap();
+            log_manager
+                .append(&set_record(2, &block_id, 300, 400))
+                .unwrap();
+            let log_sequence_number = log_manager.append(&LogRecord::Commit(2).encode()).unwrap();
+            log_manager.flush(log_sequence_number).unwrap();
+        }
+
+        write_page_with_u16(&file_manager, &checkpointed_block_id, 150);
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let page = file_manager
+            .read::<BufferPage>(&checkpointed_block_id)
+            .unwrap();
+        assert_eq!(Some(150), page.get_u16(0).unwrap());
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(400), page.get_u16(0).unwrap());
+    }
+
+    #[test]
+    fn recover_undoes_a_transaction_active_at_the_checkpoint() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+        write_page_with_u16(&file_manager, &block_id, 100);
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager.append(&LogRecord::Start(1).encode()).unwrap();
+        log_manager
+            .append(&set_record(1, &block_id, 100, 200))
+            .unwrap();
+        write_page_with_u16(&file_manager, &block_id, 200);
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        RecoveryManager::new(&buffer_manager)
+            .checkpoint(&[1])
+            .unwrap();
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(100), page.get_u16(0).unwrap());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::buffer::page::BufferPage;
+use crate::error::Error;
+use crate::file::block_id::BlockId;
+use crate::file::file_manager::FileManager;
+use crate::record::Record;
+use std::path::Path;
+
+pub(crate) type KeyValue = (Vec<u8>, Vec<u8>);
+
+// Walks the blocks of a file of sorted records lazily, so blocks past `end` are never read.
+// A block that fails to read or decode is yielded as an error and ends the scan.
+pub(crate) fn scan<'a, PathType: AsRef<Path>>(
+    file_manager: &'a FileManager<PathType>,
+    file_name: &'a str,
+    start: &'a [u8],
+    end: &'a [u8],
+) -> Result<impl Iterator<Item = Result<KeyValue, Error>> + 'a, Error> {
+    let number_of_blocks = file_manager.number_of_blocks(file_name)?;
+    let mut failed = false;
+    Ok((0..number_of_blocks)
+        .map_while(move |block_number| {
+            if failed {
+                return None;
+            }
+            let records = file_manager
+                .read::<BufferPage>(&BlockId::new(file_name, block_number))
+                .and_then(|page| Record::read_all(&page));
+            failed = records.is_err();
+            Some(records)
+        })
+        .flat_map(|records| {
+            let (records, error) = match records {
+                Ok(records) => (records, None),
+                Err(err) => (Vec::new(), Some(err)),
+            };
+            records.into_iter().map(Ok).chain(error.map(Err))
+        })
+        .skip_while(move |record| matches!(record, Ok(record) if record.key() < start))
+        .take_while(move |record| !matches!(record, Ok(record) if record.key() >= end))
+        .filter(|record| !matches!(record, Ok(record) if record.is_tombstone()))
+        .map(|record| record.map(Record::into_key_value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file::block_id::BlockId;
+    use crate::file::file_manager::FileManager;
+    use crate::memtable::Memtable;
+    use crate::sstable::scan;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE_IN_BYTES: usize = 64;
+
+    fn alphabet() -> Memtable {
+        let mut memtable = Memtable::new();
+        (b'a'..=b'z').for_each(|key| memtable.put(&[key], &[key.to_ascii_uppercase()]));
+        memtable
+    }
+
+    #[test]
+    fn scan_a_range_of_keys() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        alphabet().flush_to(&file_manager, file_name).unwrap();
+        assert!(file_manager.number_of_blocks(file_name).unwrap() > 1);
+
+        let records: Vec<(Vec<u8>, Vec<u8>)> = scan(&file_manager, file_name, b"d", b"h")
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                (b"d".to_vec(), b"D".to_vec()),
+                (b"e".to_vec(), b"E".to_vec()),
+                (b"f".to_vec(), b"F".to_vec()),
+                (b"g".to_vec(), b"G".to_vec()),
+            ],
+            records
+        );
+    }
+
+    #[test]
+    fn scan_a_range_of_keys_skipping_tombstones() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let mut memtable = alphabet();
+        memtable.delete(b"e");
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        memtable.flush_to(&file_manager, file_name).unwrap();
+
+        let keys: Vec<Vec<u8>> = scan(&file_manager, file_name, b"d", b"h")
+            .unwrap()
+            .map(|record| record.unwrap().0)
+            .collect();
+        assert_eq!(vec![b"d".to_vec(), b"f".to_vec(), b"g".to_vec()], keys);
+    }
+
+    #[test]
+    fn scan_an_empty_range() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        alphabet().flush_to(&file_manager, file_name).unwrap();
+
+        assert_eq!(
+            0,
+            scan(&file_manager, file_name, b"h", b"d").unwrap().count()
+        );
+    }
+
+    #[test]
+    fn report_a_corrupt_block_while_scanning() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let file_name = file.path().file_name().unwrap().to_str().unwrap();
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE_IN_BYTES).unwrap();
+        alphabet().flush_to(&file_manager, file_name).unwrap();
+        assert!(file_manager.number_of_blocks(file_name).unwrap() > 2);
+
+        let corrupt_block_id = BlockId::new(file_name, 1);
+        file_manager
+            .write(&corrupt_block_id, &[0xFF; BLOCK_SIZE_IN_BYTES])
+            .unwrap();
+
+        let mut records = scan(&file_manager, file_name, b"a", b"z").unwrap();
+        assert_eq!(
+            (b"a".to_vec(), b"A".to_vec()),
+            records.next().unwrap().unwrap()
+        );
+        assert!(records.by_ref().any(|record| record.is_err()));
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,364 @@
+use crate::buffer::buffer_manager::BufferManager;
+use crate::buffer::page::BufferPage;
+use crate::encodex::str_encoder_decoder::StrEncoderDecoder;
+use crate::encodex::{EncoderDecoder, U32EncoderDecoder};
+use crate::error::Error;
+use crate::file::block_id::BlockId;
+use crate::recovery::log_record::{LogRecord, SetRecord, TransactionNumber};
+use crate::recovery::recovery_manager::RecoveryManager;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static NEXT_TRANSACTION_NUMBER: AtomicU32 = AtomicU32::new(1);
+
+/// Keeps new transactions from reusing a number already present in the log.
+pub(crate) fn start_transaction_numbers_after(transaction_number: TransactionNumber) {
+    NEXT_TRANSACTION_NUMBER.fetch_max(transaction_number.saturating_add(1), Ordering::SeqCst);
+}
+
+pub(crate) struct Transaction<'b, 'l, 'a, PathType: AsRef<Path>> {
+    buffer_manager: &'b BufferManager<'l, 'a, PathType>,
+    transaction_number: TransactionNumber,
+}
+
+impl<'b, 'l, 'a, PathType: AsRef<Path>> Transaction<'b, 'l, 'a, PathType> {
+    pub(crate) fn new(
+        buffer_manager: &'b BufferManager<'l, 'a, PathType>,
+    ) -> Result<Transaction<'b, 'l, 'a, PathType>, Error> {
+        let transaction_number = NEXT_TRANSACTION_NUMBER.fetch_add(1, Ordering::SeqCst);
+        buffer_manager
+            .log_manager()
+            .append(&LogRecord::Start(transaction_number).encode())?;
+
+        Ok(Transaction {
+            buffer_manager,
+            transaction_number,
+        })
+    }
+
+    pub(crate) fn get_u32(
+        &mut self,
+        block_id: &BlockId,
+        index: usize,
+    ) -> Result<Option<u32>, Error> {
+        self.with_page(block_id, |page| page.get_u32(index))
+    }
+
+    pub(crate) fn get_string(
+        &mut self,
+        block_id: &BlockId,
+        index: usize,
+    ) -> Result<Option<String>, Error> {
+        self.with_page(block_id, |page| {
+            Ok(page.get_string(index)?.map(String::from))
+        })
+    }
+
+    pub(crate) fn set_u32(
+        &mut self,
+        block_id: &BlockId,
+        index: usize,
+        value: u32,
+    ) -> Result<(), Error> {
+        let mut after_image = vec![0; U32EncoderDecoder.bytes_needed_for_encoding(&value)];
+        U32EncoderDecoder.encode(&value, &mut after_image, 0);
+
+        self.set(block_id, index, after_image, |page| {
+            page.mutate_u32(value, index)
+        })
+    }
+
+    pub(crate) fn set_string(
+        &mut self,
+        block_id: &BlockId,
+        index: usize,
+        value: &str,
+    ) -> Result<(), Error> {
+        let mut after_image = vec![0; StrEncoderDecoder.bytes_needed_for_encoding(value)];
+        StrEncoderDecoder.encode(value, &mut after_image, 0);
+
+        self.set(block_id, index, after_image, |page| {
+            page.mutate_string(value, index)
+        })
+    }
+
+    pub(crate) fn commit(self) -> Result<(), Error> {
+        self.buffer_manager.flush_all()?;
+        let mut log_manager = self.buffer_manager.log_manager();
+        let log_sequence_number =
+            log_manager.append(&LogRecord::Commit(self.transaction_number).encode())?;
+        log_manager.flush(log_sequence_number)?;
+        Ok(())
+    }
+
+    pub(crate) fn rollback(self) -> Result<(), Error> {
+        RecoveryManager::new(self.buffer_manager).rollback(self.transaction_number)?;
+        let mut log_manager = self.buffer_manager.log_manager();
+        let log_sequence_number =
+            log_manager.append(&LogRecord::Rollback(self.transaction_number).encode())?;
+        log_manager.flush(log_sequence_number)?;
+        Ok(())
+    }
+
+    // The page stays locked from the mutation until the buffer is marked modified, so it cannot
+    // be flushed ahead of its log record, and a rejected mutation is never logged.
+    fn set<F: Fn(&mut BufferPage) -> Result<(), Error>>(
+        &mut self,
+        block_id: &BlockId,
+        index: usize,
+        after_image: Vec<u8>,
+        mutate_fn: F,
+    ) -> Result<(), Error> {
+        let pinned = self.buffer_manager.pin(block_id.clone())?;
+        let mut buffer = pinned.write();
+        let Some(page) = buffer.page() else {
+            return Err(Error::FieldNotFound { index });
+        };
+        let Some(before_image) = page.encoded_field(index).map(|encoded| encoded.to_vec()) else {
+            return Err(Error::FieldNotFound { index });
+        };
+        mutate_fn(page)?;
+
+        let appended = self.buffer_manager.log_manager().append(
+            &LogRecord::Set(SetRecord {
+                transaction_number: self.transaction_number,
+                block_id: block_id.clone(),
+                field_index: index as u16,
+                before_image: before_image.clone(),
+                after_image,
+            })
+            .encode(),
+        );
+        let log_sequence_number = match appended {
+            Ok(log_sequence_number) => log_sequence_number,
+            Err(err) => {
+                page.overwrite_encoded_field(&before_image, index)?;
+                return Err(err);
+            }
+        };
+        buffer.set_modified(self.transaction_number as isize, log_sequence_number);
+        Ok(())
+    }
+
+    fn with_page<T, F: Fn(&mut BufferPage) -> Result<Option<T>, Error>>(
+        &mut self,
+        block_id: &BlockId,
+        page_fn: F,
+    ) -> Result<Option<T>, Error> {
+        let pinned = self.buffer_manager.pin(block_id.clone())?;
+        let mut buffer = pinned.write();
+        match buffer.page() {
+            Some(page) => page_fn(page),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::buffer_manager::BufferManager;
+    use crate::buffer::page::BufferPage;
+    use crate::error::Error;
+    use crate::file::block_id::BlockId;
+    use crate::file::file_manager::FileManager;
+    use crate::log::log_manager::LogManager;
+    use crate::recovery::log_record::LogRecord;
+    use crate::recovery::recovery_manager::RecoveryManager;
+    use crate::transaction::Transaction;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    #[test]
+    fn commit_a_transaction_and_reload_the_page() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u32(100).unwrap();
+        page.add_string("Bolt-DB").unwrap();
+        file_manager.write(&block_id, page.encode()).unwrap();
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        transaction.set_u32(&block_id, 0, 200).unwrap();
+        transaction.set_string(&block_id, 1, "RocksDB").unwrap();
+        assert_eq!(Some(200), transaction.get_u32(&block_id, 0).unwrap());
+        transaction.commit().unwrap();
+
+        let reloaded_file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let page = reloaded_file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(200), page.get_u32(0).unwrap());
+        assert_eq!(Some("RocksDB"), page.get_string(1).unwrap());
+    }
+
+    #[test]
+    fn rollback_a_transaction() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u32(100).unwrap();
+        page.add_string("Bolt-DB").unwrap();
+        file_manager.write(&block_id, page.encode()).unwrap();
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        transaction.set_u32(&block_id, 0, 200).unwrap();
+        transaction.set_string(&block_id, 1, "RocksDB").unwrap();
+        transaction.rollback().unwrap();
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        assert_eq!(Some(100), transaction.get_u32(&block_id, 0).unwrap());
+        assert_eq!(
+            Some("Bolt-DB".to_string()),
+            transaction.get_string(&block_id, 1).unwrap()
+        );
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some(100), page.get_u32(0).unwrap());
+        assert_eq!(Some("Bolt-DB"), page.get_string(1).unwrap());
+    }
+
+    #[test]
+    fn set_a_string_of_a_different_length_and_rollback() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u32(100).unwrap();
+        page.add_string("Bolt-DB").unwrap();
+        page.add_u32(300).unwrap();
+        file_manager.write(&block_id, page.encode()).unwrap();
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        transaction
+            .set_string(&block_id, 1, "RocksDB is an LSM based storage engine")
+            .unwrap();
+        assert_eq!(
+            Some("RocksDB is an LSM based storage engine".to_string()),
+            transaction.get_string(&block_id, 1).unwrap()
+        );
+        assert_eq!(Some(300), transaction.get_u32(&block_id, 2).unwrap());
+
+        transaction.set_string(&block_id, 1, "LMDB").unwrap();
+        assert_eq!(
+            Some("LMDB".to_string()),
+            transaction.get_string(&block_id, 1).unwrap()
+        );
+        assert_eq!(Some(300), transaction.get_u32(&block_id, 2).unwrap());
+        transaction.rollback().unwrap();
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        assert_eq!(
+            Some("Bolt-DB".to_string()),
+            transaction.get_string(&block_id, 1).unwrap()
+        );
+        assert_eq!(Some(300), transaction.get_u32(&block_id, 2).unwrap());
+    }
+
+    #[test]
+    fn attempt_to_set_a_non_existent_field() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_u32(100).unwrap();
+        file_manager.write(&block_id, page.encode()).unwrap();
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        assert!(matches!(
+            transaction.set_u32(&block_id, 1, 200),
+            Err(Error::FieldNotFound { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn rejected_sets_are_not_logged_and_not_redone_by_recovery() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let block_id = BlockId::new(data_file_name, 0);
+
+        let mut page = BufferPage::new(BLOCK_SIZE);
+        page.add_string("Bolt-DB").unwrap();
+        file_manager.write(&block_id, page.encode()).unwrap();
+
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+
+        let mut transaction = Transaction::new(&buffer_manager).unwrap();
+        assert!(matches!(
+            transaction.set_u32(&block_id, 0, 7),
+            Err(Error::TypeMismatch { index: 0 })
+        ));
+        assert!(matches!(
+            transaction.set_string(&block_id, 0, &"r".repeat(BLOCK_SIZE)),
+            Err(Error::PageFull)
+        ));
+        transaction.commit().unwrap();
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let page = file_manager.read::<BufferPage>(&block_id).unwrap();
+        assert_eq!(Some("Bolt-DB"), page.get_string(0).unwrap());
+    }
+
+    #[test]
+    fn start_transaction_numbers_after_the_highest_one_in_the_log() {
+        let file = NamedTempFile::new().expect("Failed to create temp file");
+        let directory_path = file.path().parent().unwrap();
+        let data_file_name = file.path().file_name().unwrap().to_str().unwrap();
+        let log_file_name = format!("{}.log", data_file_name);
+
+        let file_manager = FileManager::new(directory_path, BLOCK_SIZE).unwrap();
+        let mut log_manager = LogManager::new(&file_manager, log_file_name.to_string()).unwrap();
+        log_manager
+            .append(&LogRecord::Start(1_000_000).encode())
+            .unwrap();
+        log_manager
+            .append(&LogRecord::Commit(1_000_000).encode())
+            .unwrap();
+        log_manager
+            .append(&LogRecord::Checkpoint(vec![]).encode())
+            .unwrap();
+
+        let buffer_manager = BufferManager::new(1, &mut log_manager);
+        RecoveryManager::new(&buffer_manager).recover().unwrap();
+
+        let transaction = Transaction::new(&buffer_manager).unwrap();
+        assert!(transaction.transaction_number > 1_000_000);
+    }
+}